@@ -0,0 +1,66 @@
+use conway::Conway;
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rand::{rngs::StdRng, SeedableRng};
+
+const SEED: u64 = 0xC0FFEE;
+
+/// Coordinates of a glider heading towards the bottom right.
+const GLIDER: [(usize, usize); 5] = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+fn random_board(size: usize) -> Conway {
+    let mut conway = Conway::new(size, size, StdRng::seed_from_u64(SEED));
+    conway.generate_random_board();
+    conway
+}
+
+fn glider_board(size: usize) -> Conway {
+    let mut conway = Conway::new(size, size, StdRng::seed_from_u64(SEED));
+    for (x, y) in GLIDER {
+        conway
+            .revive_cell(x + size / 2, y + size / 2)
+            .expect("Glider should fit on the board.");
+    }
+    conway
+}
+
+fn bench_tick(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tick");
+    for size in [50, 200, 500] {
+        let board = random_board(size);
+        group.bench_with_input(BenchmarkId::new("random", size), &board, |b, board| {
+            b.iter_batched(
+                || board.clone(),
+                |mut board| board.tick(),
+                BatchSize::LargeInput,
+            );
+        });
+    }
+
+    let board = glider_board(500);
+    group.bench_with_input(BenchmarkId::new("glider", 500), &board, |b, board| {
+        b.iter_batched(
+            || board.clone(),
+            |mut board| board.tick(),
+            BatchSize::LargeInput,
+        );
+    });
+
+    // After a tick the board knows where it changed, so the next tick only rechecks around the glider.
+    let mut board = glider_board(500);
+    board.tick().expect("Glider board should tick.");
+    group.bench_with_input(
+        BenchmarkId::new("glider_active", 500),
+        &board,
+        |b, board| {
+            b.iter_batched(
+                || board.clone(),
+                |mut board| board.tick(),
+                BatchSize::LargeInput,
+            );
+        },
+    );
+    group.finish();
+}
+
+criterion_group!(benches, bench_tick);
+criterion_main!(benches);
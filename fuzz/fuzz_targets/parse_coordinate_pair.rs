@@ -0,0 +1,11 @@
+#![no_main]
+
+use conway::parse_coordinate_pair;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &str| {
+    // Anything that parses must be exactly two integers, so it should survive a round trip.
+    if let Ok((x, y)) = parse_coordinate_pair(data) {
+        assert_eq!(parse_coordinate_pair(&format!("{x},{y}")), Ok((x, y)));
+    }
+});
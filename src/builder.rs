@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    cells::Cells, formats::parse_snapshot, CellState, Conway, ConwayError, LifeRule, Neighborhood,
+    Rule,
+};
+
+/// Builds a Conway board, for when there's more to configure than just its size.
+#[derive(Debug, Default)]
+pub struct ConwayBuilder {
+    width: usize,
+    height: usize,
+    rng: Option<StdRng>,
+    density: Option<f64>,
+    wrap: bool,
+    rule: Rule,
+    dying_states: Option<u8>,
+    totalistic: Option<bool>,
+    custom_rule: Option<Arc<dyn LifeRule>>,
+    neighborhood: Option<Arc<[(i32, i32)]>>,
+    hex: bool,
+}
+
+impl ConwayBuilder {
+    /// Returns a builder for an empty 0x0 board.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the width and height of the board.
+    #[must_use]
+    pub fn size(mut self, width: usize, height: usize) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Sets the rng used to generate random cells.
+    #[must_use]
+    pub fn rng(mut self, rng: StdRng) -> Self {
+        self.rng = Some(rng);
+        self
+    }
+
+    /// Seeds the rng used to generate random cells, so the same seed always generates the same board.
+    #[must_use]
+    pub fn seed(self, seed: u64) -> Self {
+        self.rng(StdRng::seed_from_u64(seed))
+    }
+
+    /// Sets whether the edges of the board wrap around to the opposite side, making the board a torus.
+    #[must_use]
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Sets the rule the board ticks by, which defaults to Conway's game of life.
+    #[must_use]
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    /// Has the board tick by a rule of its own instead of its life-like rule, which is still what exports are written with.
+    #[must_use]
+    pub fn custom_rule(mut self, rule: impl LifeRule + 'static) -> Self {
+        self.custom_rule = Some(Arc::new(rule));
+        self
+    }
+
+    /// Counts the cells at the given x, y offsets as neighbors instead of the eight surrounding cells,
+    /// such as every cell within two for a range 2 rule. Edges and wrapping work the same as usual,
+    /// and an offset listed twice counts twice. Rules only know counts up to 8, so bigger
+    /// neighborhoods need a ``custom_rule`` to go with them.
+    #[must_use]
+    pub fn neighborhood(mut self, offsets: Vec<(i32, i32)>) -> Self {
+        self.neighborhood = Some(offsets.into());
+        self
+    }
+
+    /// Sets whether the board is a hex grid, where each cell has six neighbors and odd rows sit half a cell to the right
+    /// of even ones. This takes the place of any other neighborhood. On a wrapped hex grid the height should be even,
+    /// as otherwise the top and bottom rows don't line up.
+    #[must_use]
+    pub fn hex(mut self, hex: bool) -> Self {
+        self.hex = hex;
+        self
+    }
+
+    /// Sets how many decaying states cells go through after dying, overriding the rule's own.
+    #[must_use]
+    pub fn dying_states(mut self, states: u8) -> Self {
+        self.dying_states = Some(states);
+        self
+    }
+
+    /// Sets whether an alive cell counts itself along with its neighbors, overriding the rule's own.
+    #[must_use]
+    pub fn totalistic(mut self, totalistic: bool) -> Self {
+        self.totalistic = Some(totalistic);
+        self
+    }
+
+    /// Fills the board with random cells, each having a ``density`` chance of being alive.
+    /// The density is clamped between 0 and 1.
+    #[must_use]
+    pub fn density(mut self, density: f64) -> Self {
+        self.density = Some(density.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Builds the board from a snapshot written by ``Conway::to_bytes``, which also decides its size.
+    /// Any density is ignored, as every cell comes from the snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the snapshot is from a different version, or is cut short or otherwise malformed.
+    pub fn build_from_bytes(mut self, bytes: &[u8]) -> Result<Conway, ConwayError> {
+        let (width, height, bits) = parse_snapshot(bytes)?;
+        self.density = None;
+        let mut conway = self.size(width, height).build();
+        for i in 0..conway.cells.len() {
+            if bits[i / 8] & (1 << (i % 8)) != 0 {
+                conway.cells.set(i, CellState::Alive);
+            }
+        }
+        Ok(conway)
+    }
+
+    /// Builds the board, falling back to an entropy seeded rng if none was given.
+    #[must_use]
+    pub fn build(self) -> Conway {
+        let mut rule = self.rule;
+        if let Some(states) = self.dying_states {
+            rule = rule.with_dying_states(states);
+        }
+        if let Some(totalistic) = self.totalistic {
+            rule = rule.with_totalistic(totalistic);
+        }
+        let mut conway = Conway {
+            cells: Cells::new(self.width * self.height),
+            rng: self.rng.unwrap_or_else(StdRng::from_entropy),
+            width: self.width,
+            height: self.height,
+            wrap: self.wrap,
+            rule,
+            custom_rule: self.custom_rule,
+            neighborhood: match self.neighborhood {
+                _ if self.hex => Neighborhood::Hex,
+                Some(offsets) => Neighborhood::Custom(offsets),
+                None => Neighborhood::Moore,
+            },
+            active: None,
+            births: 0,
+            deaths: 0,
+            edge_changes: 0,
+            changed_cells: 0,
+            generation: 0,
+            background: CellState::Dead,
+        };
+        if let Some(density) = self.density {
+            conway.generate_density_board(density);
+        }
+        conway
+    }
+}
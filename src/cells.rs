@@ -0,0 +1,149 @@
+#[cfg(feature = "bitpacked")]
+use bitvec::vec::BitVec;
+
+use crate::CellState;
+
+/// The storage behind a board's cells, laid out row by row.
+/// This is a byte per cell, or a single bit per cell with the ``bitpacked`` feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Cells {
+    #[cfg(not(feature = "bitpacked"))]
+    inner: Vec<CellState>,
+    #[cfg(feature = "bitpacked")]
+    inner: BitVec,
+    /// How many ticks each cell has left to decay, which only takes up room once a cell is dying.
+    #[cfg(feature = "bitpacked")]
+    dying: Vec<u8>,
+}
+
+impl Cells {
+    /// Returns storage for ``len`` dead cells.
+    pub(crate) fn new(len: usize) -> Self {
+        Self {
+            #[cfg(not(feature = "bitpacked"))]
+            inner: vec![CellState::Dead; len],
+            #[cfg(feature = "bitpacked")]
+            inner: BitVec::repeat(false, len),
+            #[cfg(feature = "bitpacked")]
+            dying: Vec::new(),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub(crate) fn get(&self, index: usize) -> Option<CellState> {
+        #[cfg(not(feature = "bitpacked"))]
+        return self.inner.get(index).copied();
+        #[cfg(feature = "bitpacked")]
+        return self
+            .inner
+            .get(index)
+            .map(|alive| to_state(*alive, self.dying.get(index).copied().unwrap_or(0)));
+    }
+
+    /// Sets the cell at ``index``.
+    ///
+    /// # Panics
+    ///
+    /// Panics if ``index`` is out of bounds.
+    pub(crate) fn set(&mut self, index: usize, state: CellState) {
+        #[cfg(not(feature = "bitpacked"))]
+        {
+            self.inner[index] = state;
+        }
+        #[cfg(feature = "bitpacked")]
+        {
+            self.inner.set(index, state == CellState::Alive);
+            match state {
+                CellState::Dying(left) => {
+                    if self.dying.is_empty() {
+                        self.dying = vec![0; self.inner.len()];
+                    }
+                    self.dying[index] = left;
+                }
+                _ if !self.dying.is_empty() => self.dying[index] = 0,
+                _ => (),
+            }
+        }
+    }
+
+    pub(crate) fn fill(&mut self, state: CellState) {
+        #[cfg(not(feature = "bitpacked"))]
+        self.inner.fill(state);
+        #[cfg(feature = "bitpacked")]
+        {
+            self.inner.fill(state == CellState::Alive);
+            match state {
+                CellState::Dying(left) => self.dying = vec![left; self.inner.len()],
+                _ => self.dying = Vec::new(),
+            }
+        }
+    }
+
+    /// Returns the amount of alive cells.
+    pub(crate) fn count_alive(&self) -> usize {
+        #[cfg(not(feature = "bitpacked"))]
+        return self
+            .inner
+            .iter()
+            .filter(|cell| matches!(cell, CellState::Alive))
+            .count();
+        #[cfg(feature = "bitpacked")]
+        return self.inner.count_ones();
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = CellState> + '_ {
+        #[cfg(not(feature = "bitpacked"))]
+        return self.inner.iter().copied();
+        #[cfg(feature = "bitpacked")]
+        return self
+            .inner
+            .iter()
+            .by_vals()
+            .enumerate()
+            .map(|(i, alive)| to_state(alive, self.dying.get(i).copied().unwrap_or(0)));
+    }
+}
+
+impl FromIterator<CellState> for Cells {
+    fn from_iter<T: IntoIterator<Item = CellState>>(iter: T) -> Self {
+        #[cfg(not(feature = "bitpacked"))]
+        return Self {
+            inner: iter.into_iter().collect(),
+        };
+        #[cfg(feature = "bitpacked")]
+        {
+            let mut cells = Self {
+                inner: BitVec::new(),
+                dying: Vec::new(),
+            };
+            for (i, state) in iter.into_iter().enumerate() {
+                cells.inner.push(state == CellState::Alive);
+                if let CellState::Dying(left) = state {
+                    // Catch up on the cells before this one, which weren't dying.
+                    cells.dying.resize(i, 0);
+                    cells.dying.push(left);
+                }
+            }
+            if !cells.dying.is_empty() {
+                cells.dying.resize(cells.inner.len(), 0);
+            }
+            cells
+        }
+    }
+}
+
+#[cfg(feature = "bitpacked")]
+fn to_state(alive: bool, dying: u8) -> CellState {
+    match (alive, dying) {
+        (true, _) => CellState::Alive,
+        (false, 0) => CellState::Dead,
+        (false, left) => CellState::Dying(left),
+    }
+}
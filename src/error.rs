@@ -0,0 +1,98 @@
+use core::fmt;
+
+/// The ways that working with a board, or parsing a pattern or rule for one, can fail.
+/// Coordinates are counted from 0, though they're shown counted from 1 like everywhere else the user sees them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConwayError {
+    /// The cell at x, y is off of a board of the given size.
+    OutOfBounds {
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    },
+    /// Some of the cells that were going to be changed together are off of a board of the given size.
+    CellsOutOfBounds {
+        cells: Vec<(usize, usize)>,
+        width: usize,
+        height: usize,
+    },
+    /// A pattern needs a board of at least ``needed_width`` by ``needed_height``, but the board is smaller.
+    PatternTooBig {
+        needed_width: usize,
+        needed_height: usize,
+        width: usize,
+        height: usize,
+    },
+    /// More cells were asked for than there are dead cells left on the board.
+    NotEnoughRoom {
+        cells: usize,
+        room: usize,
+        width: usize,
+        height: usize,
+    },
+    /// Four-fold symmetry was asked for on a board that isn't square.
+    NotSquare { width: usize, height: usize },
+    /// A rule couldn't be parsed, with why.
+    InvalidRule(String),
+    /// A pattern, replay or coordinate pair couldn't be parsed, with why.
+    ParseError(String),
+}
+
+impl fmt::Display for ConwayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds {
+                x,
+                y,
+                width,
+                height,
+            } => write!(
+                f,
+                "The coordinate pair {},{} was out of bounds for size {width}x{height}.",
+                x + 1,
+                y + 1
+            ),
+            Self::CellsOutOfBounds {
+                cells,
+                width,
+                height,
+            } => {
+                let cells: Vec<String> = cells
+                    .iter()
+                    .map(|(x, y)| format!("{},{}", x + 1, y + 1))
+                    .collect();
+                write!(
+                    f,
+                    "The coordinate pairs {} were out of bounds for size {width}x{height}.",
+                    cells.join(" ")
+                )
+            }
+            Self::PatternTooBig {
+                needed_width,
+                needed_height,
+                width,
+                height,
+            } => write!(
+                f,
+                "The pattern needs a board of at least {needed_width}x{needed_height}, but the board is {width}x{height}."
+            ),
+            Self::NotEnoughRoom {
+                cells,
+                room,
+                width,
+                height,
+            } => write!(
+                f,
+                "Unable to generate {cells} cells, the {width}x{height} board only has room for {room} more."
+            ),
+            Self::NotSquare { width, height } => write!(
+                f,
+                "Four-fold symmetry needs a square board, but the board is {width}x{height}."
+            ),
+            Self::InvalidRule(reason) | Self::ParseError(reason) => f.write_str(reason),
+        }
+    }
+}
+
+impl std::error::Error for ConwayError {}
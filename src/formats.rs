@@ -0,0 +1,695 @@
+use crate::{CellState, Conway, ConwayBuilder, ConwayError, Rule};
+
+/// A pattern loaded from a file, with its live cells relative to the pattern's top left corner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternFile {
+    /// The width of the pattern.
+    pub width: usize,
+    /// The height of the pattern.
+    pub height: usize,
+    /// The coordinates of every live cell in the pattern.
+    pub cells: Vec<(usize, usize)>,
+    /// The rule the pattern was made for, which is Conway's game of life unless the file says otherwise.
+    pub rule: Rule,
+}
+
+/// The starting point of a run, with everything needed to build the same board again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replay {
+    /// The seed of the board's rng, which anything random after the start depends on.
+    pub seed: Option<u64>,
+    /// Whether the edges of the board wrap around.
+    pub wrap: bool,
+    /// Whether the board is a hex grid.
+    pub hex: bool,
+    /// The size, rule and live cells of the board.
+    pub pattern: PatternFile,
+}
+
+/// Parses a replay written by ``Conway::to_replay``.
+/// It starts with a ``#conway replay`` line, then the ``seed = 1``, ``wrap = true`` and ``hex = true`` options,
+/// then the whole board in the run length encoded format.
+///
+/// # Errors
+///
+/// Errors if the header line is missing, if an option is invalid, or if the board isn't valid RLE.
+pub fn parse_replay(input: &str) -> Result<Replay, ConwayError> {
+    let mut lines = input.lines().map(str::trim).filter(|line| !line.is_empty());
+    if lines.next() != Some(REPLAY_HEADER) {
+        return Err(parse_error(format!(
+            "The replay is missing its {REPLAY_HEADER} header."
+        )));
+    }
+
+    let (mut seed, mut wrap, mut hex) = (None, false, false);
+    let mut board = String::new();
+    for line in lines {
+        // The options end where the board's own RLE header starts.
+        if !board.is_empty() {
+            board.push_str(line);
+            board.push('\n');
+            continue;
+        }
+        match line
+            .split_once('=')
+            .map(|(key, value)| (key.trim(), value.trim()))
+        {
+            Some(("seed", value)) => {
+                seed = Some(value.parse().map_err(|_| {
+                    parse_error(format!("Unable to parse the seed {value} in the replay."))
+                })?);
+            }
+            Some(("wrap", value)) => {
+                wrap = value.parse().map_err(|_| {
+                    parse_error(format!("Expected true or false for wrap, found {value}."))
+                })?;
+            }
+            Some(("hex", value)) => {
+                hex = value.parse().map_err(|_| {
+                    parse_error(format!("Expected true or false for hex, found {value}."))
+                })?;
+            }
+            _ => {
+                board.push_str(line);
+                board.push('\n');
+            }
+        }
+    }
+
+    Ok(Replay {
+        seed,
+        wrap,
+        hex,
+        pattern: parse_rle(&board)?,
+    })
+}
+
+/// Parses a pattern in the run length encoded format.
+/// <https://conwaylife.com/wiki/Run_Length_Encoded>
+///
+/// A ``#P x y`` comment line starts another block of pattern data with its top left corner at x, y,
+/// so one file can place several patterns. Each block ends at its ``!``.
+/// If blocks are placed at negative coordinates, everything is moved so that the top left cell is at 0,0,
+/// and the pattern grows past the size in the header if the blocks need it to.
+///
+/// # Errors
+///
+/// Errors if the header is missing or malformed, if a ``#P`` line isn't a coordinate pair, or if the pattern data is invalid.
+pub fn parse_rle(input: &str) -> Result<PatternFile, ConwayError> {
+    let mut lines = input.lines().map(str::trim).filter(|line| !line.is_empty());
+
+    let mut offset: Option<(i64, i64)> = None;
+    let header = loop {
+        let line = lines
+            .next()
+            .ok_or_else(|| parse_error("The RLE file is missing its header."))?;
+        match line.strip_prefix("#P") {
+            Some(position) => offset = Some(parse_position(position)?),
+            None if line.starts_with('#') => (),
+            None => break line,
+        }
+    };
+    let (width, height, rule) = parse_rle_header(header)?;
+
+    // Without any #P lines the pattern has to fit in the size in the header, which is checked as each run is read.
+    let positioned = offset.is_some() || input.lines().any(|line| line.trim().starts_with("#P"));
+    let bounds = (!positioned).then_some((width, height));
+    let (mut offset_x, mut offset_y) = offset.unwrap_or((0, 0));
+    let mut cells: Vec<(i64, i64)> = vec![];
+    let (mut x, mut y): (i64, i64) = (0, 0);
+    let mut run: Option<usize> = None;
+    // Whether the current block has ended, after which anything up to the next #P line is ignored.
+    let mut ended = false;
+    for line in lines {
+        if let Some(position) = line.strip_prefix("#P") {
+            (offset_x, offset_y) = parse_position(position)?;
+            (x, y) = (0, 0);
+            ended = false;
+            continue;
+        }
+        if ended || line.starts_with('#') {
+            continue;
+        }
+        for tag in line.chars() {
+            if let Some(digit) = tag.to_digit(10) {
+                let longer = run
+                    .unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|run| run.checked_add(digit as usize));
+                run = Some(longer.ok_or_else(|| {
+                    parse_error("Encountered a run length that was too long in the RLE file.")
+                })?);
+                continue;
+            }
+            let count = run.take().unwrap_or(1);
+            match tag {
+                'b' | '.' => x = run_end(x, count)?,
+                '$' => {
+                    x = 0;
+                    y = run_end(y, count)?;
+                }
+                '!' => {
+                    ended = true;
+                    break;
+                }
+                // Anything else is some kind of alive state.
+                tag if tag.is_ascii_alphabetic() => {
+                    let end = run_end(x, count)?;
+                    push_run(&mut cells, (x..end, y), (offset_x, offset_y), bounds)?;
+                    x = end;
+                }
+                tag if tag.is_whitespace() => (),
+                tag => {
+                    return Err(parse_error(format!(
+                        "Encountered invalid character {tag} in the RLE file."
+                    )))
+                }
+            }
+        }
+    }
+
+    if !positioned {
+        return Ok(PatternFile {
+            width,
+            height,
+            cells: cells
+                .into_iter()
+                .map(|(x, y)| (x as usize, y as usize))
+                .collect(),
+            rule,
+        });
+    }
+
+    let min_x = cells.iter().map(|&(x, _)| x).min().unwrap_or(0).min(0);
+    let min_y = cells.iter().map(|&(_, y)| y).min().unwrap_or(0).min(0);
+    let cells = from_top_left(&cells, (min_x, min_y))?;
+    Ok(PatternFile {
+        width: cells.iter().map(|&(x, _)| x + 1).fold(width, usize::max),
+        height: cells.iter().map(|&(_, y)| y + 1).fold(height, usize::max),
+        cells,
+        rule,
+    })
+}
+
+/// Parses a pattern in the Life 1.06 format, which lists the coordinates of every live cell.
+/// The coordinates can be negative, so they're moved so that the top left cell is at 0,0.
+/// <https://conwaylife.com/wiki/Life_1.06>
+///
+/// # Errors
+///
+/// Errors if the ``#Life 1.06`` header is missing, if a line isn't a coordinate pair,
+/// or if the cells are too far apart to fit on a board.
+pub fn parse_life106(input: &str) -> Result<PatternFile, ConwayError> {
+    let mut lines = input.lines().map(str::trim).filter(|line| !line.is_empty());
+    if lines.next() != Some("#Life 1.06") {
+        return Err(parse_error(
+            "The Life 1.06 file is missing its #Life 1.06 header.",
+        ));
+    }
+
+    let mut coordinates = vec![];
+    for line in lines.filter(|line| !line.starts_with('#')) {
+        match line.split_whitespace().collect::<Vec<&str>>()[..] {
+            [x, y] => match (x.parse::<i64>(), y.parse::<i64>()) {
+                (Ok(x), Ok(y)) => coordinates.push((x, y)),
+                _ => {
+                    return Err(parse_error(format!(
+                        "Unable to parse coordinate pair {line}."
+                    )))
+                }
+            },
+            _ => {
+                return Err(parse_error(format!(
+                    "Encountered invalid line {line} in the Life 1.06 file."
+                )))
+            }
+        }
+    }
+
+    let min_x = coordinates.iter().map(|&(x, _)| x).min().unwrap_or(0);
+    let min_y = coordinates.iter().map(|&(_, y)| y).min().unwrap_or(0);
+    let cells = from_top_left(&coordinates, (min_x, min_y))?;
+
+    Ok(PatternFile {
+        width: cells.iter().map(|&(x, _)| x + 1).max().unwrap_or(0),
+        height: cells.iter().map(|&(_, y)| y + 1).max().unwrap_or(0),
+        cells,
+        rule: Rule::default(),
+    })
+}
+
+/// Parses a pattern in the plaintext format, with ``O`` for alive cells and ``.`` for dead ones.
+/// A ``!Rule: B36/S23`` comment line sets the rule the pattern was made for, otherwise it's Conway's game of life.
+/// <https://conwaylife.com/wiki/Plaintext>
+///
+/// # Errors
+///
+/// Errors if the rule is invalid, or if a line has a character other than ``O`` or ``.``.
+pub fn parse_plaintext(input: &str) -> Result<PatternFile, ConwayError> {
+    let mut rule = Rule::default();
+    let mut rows = vec![];
+    for line in input.lines().map(str::trim_end) {
+        match line.strip_prefix('!') {
+            Some(comment) => {
+                if let Some(value) = comment.strip_prefix("Rule:") {
+                    rule = value.parse()?;
+                }
+            }
+            None => rows.push(line),
+        }
+    }
+
+    let mut cells = vec![];
+    for (y, row) in rows.iter().enumerate() {
+        for (x, tag) in row.chars().enumerate() {
+            match tag {
+                'O' => cells.push((x, y)),
+                '.' => (),
+                tag => {
+                    return Err(parse_error(format!(
+                        "Encountered invalid character {tag} in the plaintext file."
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(PatternFile {
+        width: rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0),
+        height: rows.len(),
+        cells,
+        rule,
+    })
+}
+
+impl Conway {
+    /// Returns the live part of the board in the run length encoded format, along with its rule.
+    /// The pattern is cropped to the bounding box of the live cells, so an empty board has a size of 0x0.
+    /// Use ``to_rle_full`` to keep the size of the whole board instead. Dying cells are written as dead.
+    /// <https://conwaylife.com/wiki/Run_Length_Encoded>
+    #[must_use]
+    pub fn to_rle(&self) -> String {
+        match self.bounding_box() {
+            Some((min_x, min_y, max_x, max_y)) => {
+                self.rle_region(min_x, min_y, max_x - min_x + 1, max_y - min_y + 1)
+            }
+            None => self.rle_region(0, 0, 0, 0),
+        }
+    }
+
+    /// Returns the whole board in the run length encoded format, along with its rule.
+    /// <https://conwaylife.com/wiki/Run_Length_Encoded>
+    #[must_use]
+    pub fn to_rle_full(&self) -> String {
+        self.rle_region(0, 0, self.width, self.height)
+    }
+
+    /// Returns the board in the replay format read by ``parse_replay``, which is the whole board as RLE
+    /// along with its seed and whether it wraps. Dying cells are written as dead.
+    #[must_use]
+    pub fn to_replay(&self, seed: Option<u64>) -> String {
+        let seed = seed
+            .map(|seed| format!("seed = {seed}\n"))
+            .unwrap_or_default();
+        format!(
+            "{REPLAY_HEADER}\n{seed}wrap = {}\nhex = {}\n{}",
+            self.wrap,
+            self.is_hex(),
+            self.to_rle_full()
+        )
+    }
+
+    /// Encodes the area of the board with its top left corner at x, y.
+    fn rle_region(&self, x: usize, y: usize, width: usize, height: usize) -> String {
+        let mut runs: Vec<(usize, char)> = vec![];
+        let mut push = |count: usize, tag: char| match runs.last_mut() {
+            Some((run, last)) if *last == tag => *run += count,
+            _ => runs.push((count, tag)),
+        };
+        for row_y in y..y + height {
+            if row_y > y {
+                push(1, '$');
+            }
+            let row: Vec<CellState> = (x..x + width)
+                .filter_map(|cell_x| self.cell(cell_x, row_y))
+                .collect();
+            // Dead cells at the end of a row are implied by the end of the line.
+            let end = row
+                .iter()
+                .rposition(|cell| matches!(cell, CellState::Alive))
+                .map_or(0, |i| i + 1);
+            for cell in &row[..end] {
+                match cell {
+                    CellState::Alive => push(1, 'o'),
+                    CellState::Dead | CellState::Dying(_) => push(1, 'b'),
+                }
+            }
+        }
+        // As are empty lines at the end of the pattern.
+        if matches!(runs.last(), Some((_, '$'))) {
+            runs.pop();
+        }
+
+        let mut rle = format!("x = {width}, y = {height}, rule = {}\n", self.rule);
+        let mut line = String::new();
+        for (count, tag) in runs {
+            let run = if count == 1 {
+                tag.to_string()
+            } else {
+                format!("{count}{tag}")
+            };
+            // Lines in RLE files shouldn't go past 70 characters.
+            if line.len() + run.len() > 70 {
+                rle.push_str(&line);
+                rle.push('\n');
+                line.clear();
+            }
+            line.push_str(&run);
+        }
+        rle.push_str(&line);
+        rle.push_str("!\n");
+        rle
+    }
+
+    /// Returns the board as a compact binary snapshot, to load again with ``Conway::from_bytes``.
+    /// It starts with the ``CNWY`` magic, a version byte, and the width and height as little endian u64s,
+    /// followed by a bit per cell in board order, lowest bit first. Dying cells are written as dead.
+    /// Only the size and cells are kept, not the rule, wrapping or rng.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(SNAPSHOT_HEADER_LEN + self.cells.len().div_ceil(8));
+        bytes.extend_from_slice(SNAPSHOT_MAGIC);
+        bytes.push(SNAPSHOT_VERSION);
+        bytes.extend_from_slice(&(self.width as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.height as u64).to_le_bytes());
+        let mut byte = 0;
+        for (i, cell) in self.cells.iter().enumerate() {
+            if cell == CellState::Alive {
+                byte |= 1 << (i % 8);
+            }
+            if i % 8 == 7 {
+                bytes.push(byte);
+                byte = 0;
+            }
+        }
+        if !self.cells.len().is_multiple_of(8) {
+            bytes.push(byte);
+        }
+        bytes
+    }
+
+    /// Builds a board from a snapshot written by ``to_bytes``, with the default rule and an entropy seeded rng.
+    /// Use ``ConwayBuilder::build_from_bytes`` to choose those.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the snapshot is from a different version, or is cut short or otherwise malformed.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ConwayError> {
+        ConwayBuilder::new().build_from_bytes(bytes)
+    }
+
+    /// Returns the board as plain text, one line per row with ``O`` for alive cells and ``.`` for dead ones.
+    /// This is the same as the plaintext format, without any comment lines. Dying cells are written as dead.
+    /// <https://conwaylife.com/wiki/Plaintext>
+    #[must_use]
+    pub fn render_string(&self) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            text.extend(row.map(|cell| match cell {
+                CellState::Alive => 'O',
+                CellState::Dead | CellState::Dying(_) => '.',
+            }));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+const REPLAY_HEADER: &str = "#conway replay";
+
+const SNAPSHOT_MAGIC: &[u8; 4] = b"CNWY";
+const SNAPSHOT_VERSION: u8 = 1;
+/// The magic, version, width and height.
+const SNAPSHOT_HEADER_LEN: usize = 4 + 1 + 8 + 8;
+
+/// Splits a snapshot written by ``Conway::to_bytes`` into its width, height and cell bits.
+pub(crate) fn parse_snapshot(bytes: &[u8]) -> Result<(usize, usize, &[u8]), ConwayError> {
+    if bytes.len() < SNAPSHOT_HEADER_LEN || &bytes[..4] != SNAPSHOT_MAGIC {
+        return Err(parse_error("The snapshot is missing its CNWY header."));
+    }
+    if bytes[4] != SNAPSHOT_VERSION {
+        return Err(parse_error(format!(
+            "The snapshot is version {}, but only version {SNAPSHOT_VERSION} can be read.",
+            bytes[4]
+        )));
+    }
+    let read_size = |at: usize| {
+        let mut size = [0; 8];
+        size.copy_from_slice(&bytes[at..at + 8]);
+        usize::try_from(u64::from_le_bytes(size))
+            .map_err(|_| parse_error("The snapshot's board is too big for this platform."))
+    };
+    let (width, height) = (read_size(5)?, read_size(13)?);
+    let cells = &bytes[SNAPSHOT_HEADER_LEN..];
+    let expected = width
+        .checked_mul(height)
+        .map(|len| len.div_ceil(8))
+        .ok_or_else(|| parse_error("The snapshot's board is too big for this platform."))?;
+    if cells.len() != expected {
+        return Err(parse_error(format!(
+            "The snapshot has {} bytes of cells, but a {width}x{height} board needs {expected}.",
+            cells.len()
+        )));
+    }
+    Ok((width, height, cells))
+}
+
+/// Returns the error for a pattern or replay that couldn't be parsed.
+fn parse_error(message: impl Into<String>) -> ConwayError {
+    ConwayError::ParseError(message.into())
+}
+
+/// Parses the ``x = 3, y = 3, rule = B3/S23`` header line of an RLE file into its width, height and rule.
+fn parse_rle_header(header: &str) -> Result<(usize, usize, Rule), ConwayError> {
+    let (mut width, mut height, mut rule) = (None, None, Rule::default());
+    for field in header.split(',') {
+        let (key, value) = field
+            .split_once('=')
+            .ok_or_else(|| parse_error(format!("Invalid field {field} in the RLE header.")))?;
+        let value = value.trim();
+        match key.trim() {
+            "x" => width = Some(parse_size(value)?),
+            "y" => height = Some(parse_size(value)?),
+            // Anything after a colon describes the topology of the grid, which we don't use.
+            "rule" => rule = value.split(':').next().unwrap_or(value).parse()?,
+            _ => (),
+        }
+    }
+    match (width, height) {
+        (Some(width), Some(height)) => Ok((width, height, rule)),
+        _ => Err(parse_error(
+            "The RLE header is missing the size of the pattern.",
+        )),
+    }
+}
+
+/// The most live cells a pattern file can have, so that a huge run is refused before it fills up memory.
+const MAX_PATTERN_CELLS: usize = 1 << 24;
+
+/// Returns the error for a pattern that's too big to load.
+fn too_big() -> ConwayError {
+    parse_error("The pattern is too big to load.")
+}
+
+/// Returns where a run of ``count`` cells starting at ``start`` ends.
+fn run_end(start: i64, count: usize) -> Result<i64, ConwayError> {
+    i64::try_from(count)
+        .ok()
+        .and_then(|count| start.checked_add(count))
+        .ok_or_else(too_big)
+}
+
+/// Adds a run of live cells in a row of a block placed at ``offset``, checking that it fits in ``bounds`` if there are any.
+fn push_run(
+    cells: &mut Vec<(i64, i64)>,
+    (run, y): (std::ops::Range<i64>, i64),
+    (offset_x, offset_y): (i64, i64),
+    bounds: Option<(usize, usize)>,
+) -> Result<(), ConwayError> {
+    if let Some((width, height)) = bounds {
+        let (width, height) = (width as i64, height as i64);
+        if y >= height || run.end > width {
+            // The first cell of the run that's outside.
+            let x = if y >= height {
+                run.start
+            } else {
+                run.start.max(width)
+            };
+            return Err(parse_error(format!(
+                "The cell {},{} is outside of the {width}x{height} size in the RLE header.",
+                x.saturating_add(1),
+                y.saturating_add(1)
+            )));
+        }
+    }
+    let count = (run.end - run.start) as usize;
+    if cells.len().saturating_add(count) > MAX_PATTERN_CELLS {
+        return Err(too_big());
+    }
+    let y = offset_y.checked_add(y).ok_or_else(too_big)?;
+    let start = offset_x.checked_add(run.start).ok_or_else(too_big)?;
+    let end = offset_x.checked_add(run.end).ok_or_else(too_big)?;
+    cells.extend((start..end).map(|x| (x, y)));
+    Ok(())
+}
+
+/// Moves the cells so that ``min`` is at 0,0, failing if they're too far apart to fit on a board.
+fn from_top_left(
+    cells: &[(i64, i64)],
+    (min_x, min_y): (i64, i64),
+) -> Result<Vec<(usize, usize)>, ConwayError> {
+    let from_min = |value: i64, min: i64| {
+        value
+            .checked_sub(min)
+            .and_then(|value| usize::try_from(value).ok())
+            .filter(|&value| value < usize::MAX)
+            .ok_or_else(too_big)
+    };
+    cells
+        .iter()
+        .map(|&(x, y)| Ok((from_min(x, min_x)?, from_min(y, min_y)?)))
+        .collect()
+}
+
+/// Parses the x, y position following ``#P``.
+fn parse_position(position: &str) -> Result<(i64, i64), ConwayError> {
+    match position.split_whitespace().collect::<Vec<&str>>()[..] {
+        [x, y] => match (x.parse(), y.parse()) {
+            (Ok(x), Ok(y)) => Ok((x, y)),
+            _ => Err(parse_error(format!(
+                "Unable to parse the position #P{position}."
+            ))),
+        },
+        _ => Err(parse_error(format!(
+            "Invalid position #P{position} in the RLE file."
+        ))),
+    }
+}
+
+fn parse_size(value: &str) -> Result<usize, ConwayError> {
+    value
+        .parse()
+        .map_err(|_| parse_error(format!("Unable to parse pattern size {value}.")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rle_headers_parse_with_and_without_a_rule() {
+        let pattern = parse_rle("x = 3, y = 1\n3o!").unwrap();
+        assert_eq!((pattern.width, pattern.height), (3, 1));
+        assert_eq!(pattern.cells, [(0, 0), (1, 0), (2, 0)]);
+        assert_eq!(pattern.rule, Rule::LIFE);
+
+        let pattern = parse_rle("#N Glider\nx = 3, y = 3, rule = B36/S23\nbo$2bo$3o!").unwrap();
+        assert_eq!((pattern.width, pattern.height), (3, 3));
+        assert_eq!(pattern.cells, [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        assert_eq!(pattern.rule, "B36/S23".parse().unwrap());
+    }
+
+    #[test]
+    fn rle_runs_past_the_header_size_are_refused() {
+        assert!(parse_rle("x = 2, y = 1\n3o!").is_err());
+        assert!(parse_rle("x = 2, y = 1\nb2o!").is_err());
+        assert!(parse_rle("x = 2, y = 1\no$o!").is_err());
+        assert!(parse_rle("x = 2, y = 1\n2o$!").is_ok());
+    }
+
+    #[test]
+    fn huge_rle_runs_are_refused() {
+        assert!(parse_rle("x = 3, y = 3\n4000000000o!").is_err());
+        assert!(parse_rle("#P 0 0\nx = 3, y = 3\n4000000000o!").is_err());
+        assert!(parse_rle("x = 3, y = 3\n99999999999999999999999o!").is_err());
+        assert!(parse_rle("#P 0 0\nx = 3, y = 3\n9223372036854775807b2o!").is_err());
+    }
+
+    #[test]
+    fn positioned_rle_blocks_are_moved_to_the_top_left() {
+        let pattern = parse_rle("#P -2 -1\nx = 2, y = 2\n2o!\n#P 3 1\nbo$o!").unwrap();
+        // The first block moves to 0,0, and the second moves with it by 2,1.
+        assert_eq!(pattern.cells, [(0, 0), (1, 0), (6, 2), (5, 3)]);
+        // The blocks need more room than the header's 2x2.
+        assert_eq!((pattern.width, pattern.height), (7, 4));
+    }
+
+    #[test]
+    fn boards_round_trip_through_rle() {
+        let rules = [Rule::LIFE, "B36/S23".parse().unwrap()];
+        for (seed, (width, height)) in [(1, 1), (7, 3), (10, 10), (33, 12)].into_iter().enumerate()
+        {
+            let mut conway = ConwayBuilder::new()
+                .size(width, height)
+                .seed(seed as u64)
+                .rule(rules[seed % rules.len()])
+                .build();
+            conway.generate_random_board();
+            let pattern = parse_rle(&conway.to_rle_full()).unwrap();
+            assert_eq!((pattern.width, pattern.height), (width, height));
+            assert_eq!(pattern.cells, conway.alive_cells().collect::<Vec<_>>());
+            assert_eq!(pattern.rule, conway.rule());
+        }
+    }
+
+    #[test]
+    fn life106_coordinates_are_moved_to_the_top_left() {
+        let pattern =
+            parse_life106("#Life 1.06\n#D A glider\n0 -1\n1 0\n-1 1\n0 1\n1 1\n").unwrap();
+        assert_eq!(pattern.cells, [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        assert_eq!((pattern.width, pattern.height), (3, 3));
+    }
+
+    #[test]
+    fn life106_files_need_their_header() {
+        assert!(parse_life106("0 0\n1 0\n").is_err());
+        assert!(parse_life106("#Life 1.05\n0 0\n").is_err());
+        assert!(parse_life106("\n#Life 1.06\n0 0\n").is_ok());
+    }
+
+    #[test]
+    fn life106_cells_too_far_apart_are_refused() {
+        assert!(
+            parse_life106("#Life 1.06\n-9223372036854775808 0\n9223372036854775807 0\n").is_err()
+        );
+        assert!(
+            parse_life106("#Life 1.06\n0 -9223372036854775808\n0 9223372036854775807\n").is_err()
+        );
+        assert!(parse_life106("#Life 1.06\n0 0\n9223372036854775808 0\n").is_err());
+    }
+
+    #[test]
+    fn plaintext_comments_set_the_rule() {
+        let pattern = parse_plaintext("!Name: Replicator\n!Rule:  b36/s23  \n!\n.O\n").unwrap();
+        assert_eq!(pattern.rule, "B36/S23".parse().unwrap());
+        assert_eq!(pattern.cells, [(1, 0)]);
+        assert_eq!((pattern.width, pattern.height), (2, 1));
+
+        let pattern = parse_plaintext("!Name: Rule: B2/S\nO\n").unwrap();
+        assert_eq!(pattern.rule, Rule::LIFE);
+        assert!(parse_plaintext("!Rule: B9/S23\nO\n").is_err());
+    }
+
+    #[test]
+    fn plaintext_rows_can_be_ragged() {
+        let pattern = parse_plaintext(".O   \n\nO..O\nO\n").unwrap();
+        assert_eq!(pattern.cells, [(1, 0), (0, 2), (3, 2), (0, 3)]);
+        // The widest row sets the width, and empty rows still count.
+        assert_eq!((pattern.width, pattern.height), (4, 4));
+        assert!(parse_plaintext(".O\nOx\n").is_err());
+    }
+}
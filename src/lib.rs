@@ -0,0 +1,1516 @@
+#![warn(clippy::pedantic)]
+#![allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss
+)]
+
+use std::sync::Arc;
+
+use rand::{rngs::StdRng, Rng};
+
+use crate::cells::Cells;
+
+mod builder;
+mod cells;
+mod error;
+mod formats;
+mod render;
+mod rule;
+
+pub use builder::ConwayBuilder;
+pub use error::ConwayError;
+pub use formats::{parse_life106, parse_plaintext, parse_replay, parse_rle, PatternFile, Replay};
+pub use render::RenderOptions;
+pub use rule::{LifeRule, Rule};
+
+/// Parses a coordinate pair in the form ``x,y``.
+///
+/// # Errors
+///
+/// Errors if the string is not exactly two comma separated integers.
+pub fn parse_coordinate_pair(s: &str) -> Result<(isize, isize), ConwayError> {
+    match s.split(',').collect::<Vec<&str>>()[..] {
+        [x, y] => match (x.parse::<isize>(), y.parse::<isize>()) {
+            (Ok(x), Ok(y)) => Ok((x, y)),
+            _ => Err(ConwayError::ParseError(
+                "Unable to parse coordinate pair.".to_owned(),
+            )),
+        },
+        _ => Err(ConwayError::ParseError(
+            "Encountered invalid coordinate set when parsing coordinates".to_owned(),
+        )),
+    }
+}
+
+/// Represents the current state of a cell, either alive or dead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellState {
+    Alive,
+    Dead,
+    /// A cell that died under a rule with decaying states, with how many more ticks it has before it's dead.
+    /// Dying cells don't count as live neighbors, and can't come back to life until they're dead.
+    Dying(u8),
+}
+
+/// The ways a randomly generated board can be mirrored or rotated onto itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    /// Mirrored from top to bottom.
+    Horizontal,
+    /// Mirrored from left to right.
+    Vertical,
+    /// Mirrored both from top to bottom and left to right.
+    Quadrant,
+    /// Looks the same after every quarter turn, which needs a square board.
+    FourFold,
+}
+
+/// Why a run of the board ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunEnd {
+    /// The board stopped changing, which includes every cell having died.
+    Stable,
+    /// The run reached its generation cap.
+    Capped,
+    /// The population reached the run's population target.
+    TargetReached,
+}
+
+/// A population for a run to stop at, for finding when a pattern grows past or collapses below some size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopulationTarget {
+    /// Stop once there are at least this many live cells.
+    AtLeast(usize),
+    /// Stop once there are fewer than this many live cells.
+    Below(usize),
+}
+
+impl PopulationTarget {
+    /// Returns whether a board with ``population`` live cells has reached the target.
+    #[must_use]
+    pub fn is_met(self, population: usize) -> bool {
+        match self {
+            Self::AtLeast(target) => population >= target,
+            Self::Below(target) => population < target,
+        }
+    }
+}
+
+/// What happened over a run of the board, see ``Conway::advance``.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunSummary {
+    /// How many generations have been run. This can start above 0 to carry on counting from an earlier run,
+    /// in which case the generation cap and ``peak_generation`` count from the same place.
+    pub generations: u64,
+    /// The population of the board as of the last generation.
+    pub population: usize,
+    /// The highest population the board had, including before the first generation.
+    pub peak_population: usize,
+    /// The generation the board first reached its peak population at.
+    pub peak_generation: u64,
+    /// How many cells were born over the whole run.
+    pub births: usize,
+    /// How many cells died over the whole run.
+    pub deaths: usize,
+    /// Why the run ended, or ``None`` while it's still going.
+    pub end: Option<RunEnd>,
+}
+
+impl RunSummary {
+    fn record_population(&mut self, population: usize) {
+        self.population = population;
+        if population > self.peak_population {
+            self.peak_population = population;
+            self.peak_generation = self.generations;
+        }
+    }
+}
+
+/// Representation of a Conway's game of life board.
+///
+/// Every random generation method draws from the board's own rng in a fixed order,
+/// so two boards of the same size with the same seed will always generate the same cells.
+/// This only holds for a given version of ``rand``, as ``StdRng`` doesn't promise a stable algorithm.
+#[derive(Clone)]
+pub struct Conway {
+    cells: Cells,
+    rng: StdRng,
+    width: usize,
+    height: usize,
+    wrap: bool,
+    rule: Rule,
+    custom_rule: Option<Arc<dyn LifeRule>>,
+    neighborhood: Neighborhood,
+    /// The cells that changed on the last tick, as only they and their neighbors can change on the next one.
+    /// This is ``None`` when the whole board has to be checked, such as after it was generated.
+    active: Option<Vec<usize>>,
+    births: usize,
+    deaths: usize,
+    edge_changes: usize,
+    changed_cells: usize,
+    /// How many ticks have changed the board since it was built or last cleared.
+    generation: u64,
+    /// The state of every cell past the edge of a board that doesn't wrap. This is dead unless the rule has B0,
+    /// where empty space comes alive and the area past the edge does too.
+    background: CellState,
+}
+
+/// How many cells in from the edge of the board count as being near it.
+const EDGE_DISTANCE: usize = 2;
+
+/// How much of the board can be near a change before ticking goes back to checking every cell,
+/// as a fraction of its cells. Past this, scanning the whole board in order is quicker.
+const ACTIVE_FRACTION: usize = 4;
+
+/// Represents coordinates of neighbors in the form of offset of x, y
+const NEIGHBOR_COORDINATES: [(i32, i32); 8] = [
+    (-1, -1), // Top Left
+    (0, -1),  // Above
+    (1, -1),  // Top Right
+    (-1, 0),  // Left
+    (1, 0),   // Right
+    (-1, 1),  // Bottom Left
+    (0, 1),   // Below
+    (1, 1),   // Bottom Right
+];
+
+/// Offsets of the six neighbors of a cell in an even row of a hex grid, where odd rows sit half a cell to the right.
+const HEX_EVEN_ROW: [(i32, i32); 6] = [(-1, -1), (0, -1), (-1, 0), (1, 0), (-1, 1), (0, 1)];
+
+/// Offsets of the six neighbors of a cell in an odd row of a hex grid.
+const HEX_ODD_ROW: [(i32, i32); 6] = [(0, -1), (1, -1), (-1, 0), (1, 0), (0, 1), (1, 1)];
+
+/// The cells that count as a cell's neighbors.
+#[derive(Debug, Clone, Default)]
+enum Neighborhood {
+    /// The eight surrounding cells.
+    #[default]
+    Moore,
+    /// The cells at the given offsets.
+    Custom(Arc<[(i32, i32)]>),
+    /// The six surrounding cells of a hex grid, where odd rows sit half a cell to the right of even ones.
+    Hex,
+}
+
+impl Conway {
+    /// Returns a Conway's board with the size of x, y
+    #[must_use]
+    pub fn new(width: usize, height: usize, rng: StdRng) -> Self {
+        ConwayBuilder::new().size(width, height).rng(rng).build()
+    }
+
+    /// Brings the cell at x, y to life, skipping it if it's already alive.
+    /// Returns whether the cell was brought to life.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the coordinates are out of bounds for the board.
+    pub fn revive_cell(&mut self, x: usize, y: usize) -> Result<bool, ConwayError> {
+        let Some(cell) = self.cell(x, y) else {
+            return Err(self.out_of_bounds(x, y));
+        };
+        if matches!(cell, CellState::Alive) {
+            Ok(false)
+        } else {
+            self.set_cell(x, y, CellState::Alive)?;
+            Ok(true)
+        }
+    }
+
+    /// Checks that a pattern with its top left corner moved to ``offset`` fits on the board.
+    ///
+    /// # Errors
+    ///
+    /// Errors with the size the pattern needs if it goes past the right or bottom edge of the board.
+    pub fn check_fits(
+        &self,
+        coords: &[(usize, usize)],
+        (offset_x, offset_y): (usize, usize),
+    ) -> Result<(), ConwayError> {
+        // The size needed along one axis, which is one past the furthest cell.
+        let needed = |offset: usize, n: usize| offset.checked_add(n)?.checked_add(1);
+        let (mut needed_width, mut needed_height) = (0, 0);
+        for &(x, y) in coords {
+            let Some((needed_x, needed_y)) = needed(offset_x, x).zip(needed(offset_y, y)) else {
+                // Past the end of a usize, which is further than any board goes.
+                needed_width = usize::MAX;
+                needed_height = usize::MAX;
+                break;
+            };
+            needed_width = needed_width.max(needed_x);
+            needed_height = needed_height.max(needed_y);
+        }
+        if needed_width > self.width || needed_height > self.height {
+            Err(ConwayError::PatternTooBig {
+                needed_width,
+                needed_height,
+                width: self.width,
+                height: self.height,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Brings a pattern to life with its top left corner moved to ``offset``.
+    /// Returns how many of its cells were already alive.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the pattern doesn't fit on the board at the offset, in which case nothing is changed.
+    pub fn place(
+        &mut self,
+        coords: &[(usize, usize)],
+        offset: (usize, usize),
+    ) -> Result<usize, ConwayError> {
+        self.check_fits(coords, offset)?;
+        let coords: Vec<(usize, usize)> = coords
+            .iter()
+            .map(|&(x, y)| (x + offset.0, y + offset.1))
+            .collect();
+        self.revive_cells(&coords)
+    }
+
+    /// Brings every cell in ``coords`` to life, skipping the ones that are already alive.
+    /// Nothing is changed if any of the coordinates are out of bounds.
+    /// Returns how many of the cells were already alive.
+    ///
+    /// # Errors
+    ///
+    /// Errors with every out of bounds coordinate pair if there are any.
+    pub fn revive_cells(&mut self, coords: &[(usize, usize)]) -> Result<usize, ConwayError> {
+        self.check_in_bounds(coords)?;
+        let mut skipped = 0;
+        for &(x, y) in coords {
+            if self.cell(x, y) == Some(CellState::Alive) {
+                skipped += 1;
+            } else {
+                self.set_cell(x, y, CellState::Alive)?;
+            }
+        }
+        Ok(skipped)
+    }
+
+    /// Kills every cell in ``coords``, such as to keep part of a random board empty.
+    /// Nothing is changed if any of the coordinates are out of bounds.
+    ///
+    /// # Errors
+    ///
+    /// Errors with every out of bounds coordinate pair if there are any.
+    pub fn kill_cells(&mut self, coords: &[(usize, usize)]) -> Result<(), ConwayError> {
+        self.check_in_bounds(coords)?;
+        for &(x, y) in coords {
+            self.set_cell(x, y, CellState::Dead)?;
+        }
+        Ok(())
+    }
+
+    /// Errors with every coordinate pair in ``coords`` that's off of the board, if there are any.
+    fn check_in_bounds(&self, coords: &[(usize, usize)]) -> Result<(), ConwayError> {
+        let out_of_bounds: Vec<(usize, usize)> = coords
+            .iter()
+            .copied()
+            .filter(|&(x, y)| x >= self.width || y >= self.height)
+            .collect();
+        if out_of_bounds.is_empty() {
+            Ok(())
+        } else {
+            Err(ConwayError::CellsOutOfBounds {
+                cells: out_of_bounds,
+                width: self.width,
+                height: self.height,
+            })
+        }
+    }
+
+    /// Randomly generates a board with a given amount of cells.
+    ///
+    /// # Errors
+    ///
+    /// Errors if there aren't enough dead cells left on the board to place them all,
+    /// or if a generated cell could not be set.
+    pub fn generate_board(&mut self, cells: usize) -> Result<(), ConwayError> {
+        let room = self.cells.len() - self.cells.count_alive();
+        if cells > room {
+            return Err(ConwayError::NotEnoughRoom {
+                cells,
+                room,
+                width: self.width,
+                height: self.height,
+            });
+        }
+        self.generation = 0;
+        for _ in 0..cells {
+            loop {
+                let x = self.rng.gen_range(0..self.width);
+                let y = self.rng.gen_range(0..self.height);
+                if let Some(cell) = self.cell(x, y) {
+                    // if the cell is not already alive, then make it so
+                    match cell {
+                        CellState::Alive => (),
+                        CellState::Dead | CellState::Dying(_) => {
+                            self.set_cell(x, y, CellState::Alive)?;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Randomly generates a board where each cell has a 50% chance of being alive.
+    pub fn generate_random_board(&mut self) {
+        self.active = None;
+        self.generation = 0;
+        for i in 0..self.cells.len() {
+            if self.rng.gen_range(0..=1) == 0 {
+                self.cells.set(i, CellState::Alive);
+            }
+        }
+    }
+
+    /// Randomly generates a board where each cell has a ``density`` chance of being alive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if ``density`` is not between 0 and 1.
+    pub fn generate_density_board(&mut self, density: f64) {
+        self.active = None;
+        self.generation = 0;
+        for i in 0..self.cells.len() {
+            if self.rng.gen_bool(density) {
+                self.cells.set(i, CellState::Alive);
+            }
+        }
+    }
+
+    /// Randomly generates a board with live cells grouped into blobs around ``clusters`` random centers.
+    /// Cells within ``radius`` of a center are revived with a chance that fades the further out they are.
+    pub fn generate_clustered_board(&mut self, clusters: usize, radius: usize) {
+        self.generation = 0;
+        if self.cells.is_empty() {
+            return;
+        }
+        self.active = None;
+        let radius = radius.max(1);
+        for _ in 0..clusters {
+            let center_x = self.rng.gen_range(0..self.width);
+            let center_y = self.rng.gen_range(0..self.height);
+            for y in center_y.saturating_sub(radius)..(center_y + radius + 1).min(self.height) {
+                for x in center_x.saturating_sub(radius)..(center_x + radius + 1).min(self.width) {
+                    let distance =
+                        (x.abs_diff(center_x).pow(2) + y.abs_diff(center_y).pow(2)) as f64;
+                    let falloff = 1.0 - distance.sqrt() / radius as f64;
+                    if falloff > 0.0 && self.rng.gen_bool(0.6 * falloff) {
+                        self.cells.set(x + y * self.width, CellState::Alive);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Randomly generates a board with a 50% chance per cell that is symmetric in the given way.
+    ///
+    /// # Errors
+    ///
+    /// Errors if four-fold symmetry is asked for on a board that isn't square.
+    pub fn generate_symmetric_board(&mut self, symmetry: Symmetry) -> Result<(), ConwayError> {
+        if symmetry == Symmetry::FourFold && self.width != self.height {
+            return Err(ConwayError::NotSquare {
+                width: self.width,
+                height: self.height,
+            });
+        }
+        self.generation = 0;
+        if self.cells.is_empty() {
+            return Ok(());
+        }
+        let (max_x, max_y) = (self.width - 1, self.height - 1);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let images = match symmetry {
+                    Symmetry::Horizontal => vec![(x, y), (x, max_y - y)],
+                    Symmetry::Vertical => vec![(x, y), (max_x - x, y)],
+                    Symmetry::Quadrant => {
+                        vec![
+                            (x, y),
+                            (max_x - x, y),
+                            (x, max_y - y),
+                            (max_x - x, max_y - y),
+                        ]
+                    }
+                    Symmetry::FourFold => {
+                        vec![
+                            (x, y),
+                            (max_x - y, x),
+                            (max_x - x, max_y - y),
+                            (y, max_y - x),
+                        ]
+                    }
+                };
+                // Only draw for the first cell of each group, the rest are copies of it.
+                if images.iter().any(|&(i, j)| (j, i) < (y, x)) {
+                    continue;
+                }
+                if self.rng.gen_range(0..=1) == 0 {
+                    for (i, j) in images {
+                        self.set_cell(i, j, CellState::Alive)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Kills every cell on the board, and starts counting generations from 0 again.
+    /// The births, deaths and other counts of the last tick are reset too, so the board is as if it was just built,
+    /// but its cells are filled in place rather than made again.
+    pub fn clear(&mut self) {
+        self.cells.fill(CellState::Dead);
+        self.active = None;
+        self.births = 0;
+        self.deaths = 0;
+        self.edge_changes = 0;
+        self.changed_cells = 0;
+        self.generation = 0;
+        self.background = CellState::Dead;
+    }
+
+    /// Returns the amount of neighbors that a cell has that are currently alive.
+    /// On a wrapped board less than 3 cells across, the same cell can be counted more than once, even the cell itself.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the coordinates are out of bounds for the board.
+    pub fn neighbors(&self, x: usize, y: usize) -> Result<usize, ConwayError> {
+        if self.cell(x, y).is_none() {
+            return Err(self.out_of_bounds(x, y));
+        }
+        Ok(self.count_neighbors(x, y))
+    }
+
+    /// Returns the amount of live neighbors the cell at x, y has, or `None` if it's off the board.
+    /// Edges are treated the same way as when ticking, so neighbors across the edge only count on a wrapped board,
+    /// or when a B0 rule has brought the area past the edge to life. Counts stop at 255,
+    /// which only a custom neighborhood can reach.
+    #[must_use]
+    pub fn live_neighbors(&self, x: usize, y: usize) -> Option<u8> {
+        self.cell(x, y)?;
+        Some(u8::try_from(self.count_neighbors(x, y)).unwrap_or(u8::MAX))
+    }
+
+    /// Returns whether the board is a hex grid, where odd rows sit half a cell to the right of even ones.
+    #[must_use]
+    pub fn is_hex(&self) -> bool {
+        matches!(self.neighborhood, Neighborhood::Hex)
+    }
+
+    /// Returns the offsets of the cells that count as neighbors of a cell in row ``y``, which are the eight
+    /// surrounding cells unless the board was built with a neighborhood of its own.
+    fn neighbor_offsets(&self, y: usize) -> &[(i32, i32)] {
+        match &self.neighborhood {
+            Neighborhood::Moore => &NEIGHBOR_COORDINATES,
+            Neighborhood::Custom(offsets) => offsets,
+            Neighborhood::Hex if y.is_multiple_of(2) => &HEX_EVEN_ROW,
+            Neighborhood::Hex => &HEX_ODD_ROW,
+        }
+    }
+
+    /// Returns the indices of the cells that count the cell at x, y as one of their neighbors.
+    fn counted_by(&self, x: usize, y: usize) -> impl Iterator<Item = usize> + '_ {
+        // A cell counts the cells at its offsets, so it's counted by the cells at the opposite offsets.
+        // Hex offsets depend on the row, so each one only counts if it's an offset of the row it leads back to.
+        // Wrapping an odd amount of rows puts two rows of the same parity next to each other, where that row
+        // isn't always the one across from this cell.
+        let rows: [&[(i32, i32)]; 2] = if self.is_hex() {
+            [&HEX_EVEN_ROW, &HEX_ODD_ROW]
+        } else {
+            [self.neighbor_offsets(y), &[]]
+        };
+        rows.into_iter()
+            .enumerate()
+            .flat_map(move |(parity, offsets)| {
+                offsets
+                    .iter()
+                    .filter_map(move |&(offset_x, offset_y)| {
+                        self.neighbor_index(x, y, (-offset_x, -offset_y))
+                    })
+                    .filter(move |&i| !self.is_hex() || (i / self.width) % 2 == parity)
+            })
+    }
+
+    /// Counts the alive neighbors of a cell, without checking that the cell is on the board.
+    fn count_neighbors(&self, x: usize, y: usize) -> usize {
+        self.neighbor_offsets(y)
+            .iter()
+            .filter(|&&offset| match self.neighbor_index(x, y, offset) {
+                Some(i) => self.cells.get(i) == Some(CellState::Alive),
+                None => self.background == CellState::Alive,
+            })
+            .count()
+    }
+
+    /// Counts the neighbors of a cell that are past the edge of the board.
+    fn outside_neighbors(&self, x: usize, y: usize) -> usize {
+        self.neighbor_offsets(y)
+            .iter()
+            .filter(|&&offset| self.neighbor_index(x, y, offset).is_none())
+            .count()
+    }
+
+    /// Returns the state the area past the edge changes to on the next tick, where every cell has the same state
+    /// and so has either every neighbor alive or none of them.
+    fn next_background(&self) -> CellState {
+        if self.wrap {
+            return CellState::Dead;
+        }
+        let neighbors = if self.background == CellState::Alive {
+            self.neighbor_offsets(0).len()
+        } else {
+            0
+        };
+        self.next_state(self.background, neighbors)
+    }
+
+    /// Returns the index of the neighbor at the given offset from x, y, or `None` if it's off the board.
+    fn neighbor_index(
+        &self,
+        x: usize,
+        y: usize,
+        (offset_x, offset_y): (i32, i32),
+    ) -> Option<usize> {
+        let (width, height) = (self.width as i32, self.height as i32);
+        let mut neighbor_x = (x as i32) + offset_x;
+        let mut neighbor_y = (y as i32) + offset_y;
+        if self.wrap {
+            // Wrap around to the opposite edge, which also takes corners to the opposite corner.
+            neighbor_x = neighbor_x.rem_euclid(width);
+            neighbor_y = neighbor_y.rem_euclid(height);
+        } else if !(0..width).contains(&neighbor_x) || !(0..height).contains(&neighbor_y) {
+            // Otherwise anything past the edge (i.e -1 or the width) doesn't exist, so skip it.
+            return None;
+        }
+
+        Some(neighbor_x as usize + neighbor_y as usize * self.width)
+    }
+
+    /// Counts the alive neighbors of every cell in one pass over the board.
+    /// Each alive cell adds one to each of its neighbors, so dead areas cost next to nothing.
+    /// Counts stop at 255, which only a custom neighborhood can reach.
+    fn neighbor_counts(&self) -> Vec<u8> {
+        let width = self.width;
+        let mut counts = vec![0u8; self.cells.len()];
+        for (i, cell) in self.cells.iter().enumerate() {
+            if cell != CellState::Alive {
+                continue;
+            }
+            let (x, y) = (i % width, i / width);
+            let interior = x > 0 && y > 0 && x + 1 < width && y + 1 < self.height;
+            if interior && matches!(self.neighborhood, Neighborhood::Moore) {
+                // Interior cells have all eight neighbors, so their indices can be used directly.
+                for neighbor in [
+                    i - width - 1,
+                    i - width,
+                    i - width + 1,
+                    i - 1,
+                    i + 1,
+                    i + width - 1,
+                    i + width,
+                    i + width + 1,
+                ] {
+                    counts[neighbor] += 1;
+                }
+            } else {
+                for neighbor in self.counted_by(x, y) {
+                    counts[neighbor] = counts[neighbor].saturating_add(1);
+                }
+            }
+        }
+        if self.background == CellState::Alive {
+            // The cells past the edge are all alive, so cells along it count every neighbor they'd have there.
+            for (i, count) in counts.iter_mut().enumerate() {
+                let outside = self.outside_neighbors(i % width, i / width);
+                *count = count.saturating_add(u8::try_from(outside).unwrap_or(u8::MAX));
+            }
+        }
+
+        counts
+    }
+
+    /// Returns the cells of the next generation in board order.
+    fn next_cells(&self) -> impl Iterator<Item = CellState> + '_ {
+        self.cells
+            .iter()
+            .zip(self.neighbor_counts())
+            .map(|(cell, neighbors)| self.next_state(cell, neighbors.into()))
+    }
+
+    /// Returns the state a cell changes to by the board's rule.
+    fn next_state(&self, cell: CellState, neighbors: usize) -> CellState {
+        match &self.custom_rule {
+            Some(rule) => rule.next_state(cell, neighbors),
+            None => self.rule.next_state(cell, neighbors),
+        }
+    }
+
+    /// Returns the changes the next tick makes as pairs of an index and the cell's new state, in board order.
+    /// Only the cells that changed last tick and their neighbors are checked when there are few enough of them,
+    /// since any other cell has the same state and neighbors it had last tick, and so stays the same.
+    fn next_changes(&self) -> Vec<(usize, CellState)> {
+        let neighbors = self.neighbor_offsets(0).len();
+        let Some(active) = self
+            .active
+            .as_ref()
+            .filter(|active| active.len() * (neighbors + 1) < self.cells.len() / ACTIVE_FRACTION)
+        else {
+            return self
+                .cells
+                .iter()
+                .zip(self.next_cells())
+                .enumerate()
+                .filter(|(_, (before, after))| before != after)
+                .map(|(i, (_, after))| (i, after))
+                .collect();
+        };
+
+        let mut candidates = active.clone();
+        for &i in active {
+            candidates.extend(self.counted_by(i % self.width, i / self.width));
+        }
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates
+            .into_iter()
+            .filter_map(|i| {
+                let before = self.cells.get(i)?;
+                // Matches the full scan, where counts stop at 255.
+                let neighbors = self
+                    .count_neighbors(i % self.width, i / self.width)
+                    .min(u8::MAX.into());
+                let after = self.next_state(before, neighbors);
+                (before != after).then_some((i, after))
+            })
+            .collect()
+    }
+
+    /// Returns whether the edges of the board wrap around to the opposite side.
+    #[must_use]
+    pub fn wraps(&self) -> bool {
+        self.wrap
+    }
+
+    /// Returns the rule the board ticks by.
+    /// A board built with a custom rule ticks by that instead, and this is only the rule that exports say it uses.
+    #[must_use]
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// Returns the width of the board.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the board.
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the state of the cell at x, y, or ``None`` if it's out of bounds.
+    #[must_use]
+    pub fn cell(&self, x: usize, y: usize) -> Option<CellState> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.cells.get(x + y * self.width)
+    }
+
+    /// Returns the amount of cells that are currently alive.
+    #[must_use]
+    pub fn population(&self) -> usize {
+        self.cells.count_alive()
+    }
+
+    /// Returns the amount of cells that came alive during the last tick.
+    #[must_use]
+    pub fn births(&self) -> usize {
+        self.births
+    }
+
+    /// Returns the amount of cells that died during the last tick.
+    #[must_use]
+    pub fn deaths(&self) -> usize {
+        self.deaths
+    }
+
+    /// Returns the amount of cells within a couple of cells of the edge that came alive or died during the last tick.
+    #[must_use]
+    pub fn edge_changes(&self) -> usize {
+        self.edge_changes
+    }
+
+    /// Returns the amount of cells that changed during the last tick, which is the Hamming distance between the last
+    /// two generations. Unlike births and deaths, this also counts dying cells decaying.
+    /// It staying low is a sign the board is settling down.
+    #[must_use]
+    pub fn changed_cells(&self) -> usize {
+        self.changed_cells
+    }
+
+    /// Returns how many generations have gone by since the board was built, last cleared or randomly generated.
+    /// Only ticks that changed the board count, so a board that has stopped stays on the same generation.
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns the rows of the board top to bottom, each going left to right.
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = CellState> + '_> + '_ {
+        (0..self.height).map(move |y| (0..self.width).filter_map(move |x| self.cell(x, y)))
+    }
+
+    /// Returns the coordinates of every live cell, going along each row from the top.
+    pub fn alive_cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| *cell == CellState::Alive)
+            .map(move |(i, _)| (i % width, i / width))
+    }
+
+    /// Returns the smallest area holding every live cell as ``(min_x, min_y, max_x, max_y)``, inclusive.
+    /// This will return ``None`` if there are no live cells.
+    #[must_use]
+    pub fn bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
+        self.alive_cells()
+            .fold(None, |bounds, (x, y)| match bounds {
+                None => Some((x, y, x, y)),
+                Some((min_x, min_y, max_x, max_y)) => {
+                    Some((min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)))
+                }
+            })
+    }
+
+    /// Returns how far this board's live cells would have to move to match ``other``'s, as ``(dx, dy)``,
+    /// if they're the same shape. Both boards' live cells are compared from the top left of their bounding boxes,
+    /// so a spaceship matches itself a period later, moved over by however far it travelled.
+    /// This will return ``None`` if the shapes differ or either board has no live cells.
+    #[must_use]
+    pub fn translation_to(&self, other: &Conway) -> Option<(isize, isize)> {
+        let (min_x, min_y, ..) = self.bounding_box()?;
+        let (other_x, other_y, ..) = other.bounding_box()?;
+        // Live cells come in board order, so the same shape comes in the same order on either board.
+        let shape = |conway: &'_ Conway, (left, top): (usize, usize)| {
+            conway
+                .alive_cells()
+                .map(move |(x, y)| (x - left, y - top))
+                .collect::<Vec<_>>()
+        };
+        (shape(self, (min_x, min_y)) == shape(other, (other_x, other_y))).then(|| {
+            (
+                other_x as isize - min_x as isize,
+                other_y as isize - min_y as isize,
+            )
+        })
+    }
+
+    /// Moves every cell so the live cells' bounding box is in the middle of the board, keeping how they're arranged.
+    /// Decaying cells move along with them, unless they'd end up off of the board. On a hex grid cells only move by
+    /// an even amount of rows, as moving by an odd amount would change which cells are neighbors.
+    /// This will return ``false`` if there are no live cells or they're already in the middle.
+    pub fn recenter(&mut self) -> bool {
+        let Some((min_x, min_y, max_x, max_y)) = self.bounding_box() else {
+            return false;
+        };
+        let shift = |min: usize, max: usize, size: usize| {
+            (size - (max - min + 1)) as isize / 2 - min as isize
+        };
+        let shift_x = shift(min_x, max_x, self.width);
+        let mut shift_y = shift(min_y, max_y, self.height);
+        if self.is_hex() {
+            shift_y -= shift_y % 2;
+        }
+        if shift_x == 0 && shift_y == 0 {
+            return false;
+        }
+
+        let moved: Vec<(usize, usize, CellState)> = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter_map(|(x, y)| {
+                self.cell(x, y)
+                    .filter(|&cell| cell != CellState::Dead)
+                    .map(|cell| (x, y, cell))
+            })
+            .collect();
+        for &(x, y, _) in &moved {
+            self.cells.set(x + y * self.width, CellState::Dead);
+        }
+        for (x, y, cell) in moved {
+            let x = x.checked_add_signed(shift_x).filter(|&x| x < self.width);
+            let y = y.checked_add_signed(shift_y).filter(|&y| y < self.height);
+            if let (Some(x), Some(y)) = (x, y) {
+                self.cells.set(x + y * self.width, cell);
+            }
+        }
+        // Every cell could have changed, so the next tick looks at all of them.
+        self.active = None;
+        true
+    }
+
+    fn set_cell(&mut self, x: usize, y: usize, state: CellState) -> Result<(), ConwayError> {
+        if x >= self.width || y >= self.height {
+            return Err(self.out_of_bounds(x, y));
+        }
+        let i = x + y * self.width;
+        self.cells.set(i, state);
+        if let Some(active) = &mut self.active {
+            active.push(i);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the error for x, y being off of this board.
+    fn out_of_bounds(&self, x: usize, y: usize) -> ConwayError {
+        ConwayError::OutOfBounds {
+            x,
+            y,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    fn is_near_edge(&self, x: usize, y: usize) -> bool {
+        x < EDGE_DISTANCE
+            || y < EDGE_DISTANCE
+            || x + EDGE_DISTANCE >= self.width
+            || y + EDGE_DISTANCE >= self.height
+    }
+
+    /// Returns whether the next tick would leave the board as it is.
+    #[must_use]
+    pub fn is_stable(&self) -> bool {
+        self.next_cells().eq(self.cells.iter())
+    }
+
+    /// Returns whether the next tick would change any cell on the board.
+    #[must_use]
+    pub fn will_change(&self) -> bool {
+        !self.is_stable()
+    }
+
+    /// Ticks the board until it stops changing, ``max_gen`` generations have run or the population reaches ``target``,
+    /// without any waiting or output.
+    ///
+    /// # Errors
+    ///
+    /// Errors if ticking does.
+    pub fn run_headless(
+        &mut self,
+        max_gen: Option<u64>,
+        target: Option<PopulationTarget>,
+    ) -> Result<RunSummary, ConwayError> {
+        let mut summary = RunSummary::default();
+        while self.advance(&mut summary, max_gen, target)? {}
+        Ok(summary)
+    }
+
+    /// Runs the next generation of a run, adding it to ``summary``, unless the run is over.
+    /// This will return ``false`` once the run is over, with ``summary.end`` saying why, else it will return ``true``.
+    /// Calling this in a loop is the same as ``run_headless``, with room to do something between generations.
+    ///
+    /// # Errors
+    ///
+    /// Errors if ticking does.
+    pub fn advance(
+        &mut self,
+        summary: &mut RunSummary,
+        max_gen: Option<u64>,
+        target: Option<PopulationTarget>,
+    ) -> Result<bool, ConwayError> {
+        summary.record_population(self.population());
+        // The starting board counts too, so a run that begins on the target doesn't tick at all.
+        if target.is_some_and(|target| target.is_met(summary.population)) {
+            summary.end = Some(RunEnd::TargetReached);
+            return Ok(false);
+        }
+        if max_gen.is_some_and(|max_gen| summary.generations >= max_gen) {
+            summary.end = Some(RunEnd::Capped);
+            return Ok(false);
+        }
+        if !self.tick()? {
+            summary.end = Some(RunEnd::Stable);
+            return Ok(false);
+        }
+        summary.generations += 1;
+        summary.record_population(self.population());
+        summary.births += self.births;
+        summary.deaths += self.deaths;
+        Ok(true)
+    }
+
+    /// Writes the next generation into ``out`` without changing this board, resizing ``out`` to fit.
+    /// This will return ``true`` if any cell changed, else it will return ``false``.
+    pub fn tick_into(&self, out: &mut Vec<CellState>) -> bool {
+        out.clear();
+        out.extend(self.next_cells());
+        out.iter().copied().ne(self.cells.iter())
+    }
+
+    /// Ticks the game board, checking if the next set of cells is alive.
+    /// This will return ``true`` if the game managed to tick, else it will return ``false``.
+    ///
+    /// # Errors
+    ///
+    /// Errors if a cell was somehow out of bounds while ticking.
+    pub fn tick(&mut self) -> Result<bool, ConwayError> {
+        let changes = self.next_changes();
+
+        self.births = 0;
+        self.deaths = 0;
+        self.edge_changes = 0;
+        for &(i, after) in &changes {
+            let (x, y) = (i % self.width, i / self.width);
+            let before = self.cells.get(i).ok_or_else(|| self.out_of_bounds(x, y))?;
+            self.cells.set(i, after);
+            match (before, after) {
+                (CellState::Dead, CellState::Alive) => self.births += 1,
+                (CellState::Alive, CellState::Dead | CellState::Dying(_)) => self.deaths += 1,
+                _ => continue,
+            }
+            if self.is_near_edge(x, y) {
+                self.edge_changes += 1;
+            }
+        }
+        self.changed_cells = changes.len();
+        let background = self.next_background();
+        // Dying cells can change while nothing is born or dies, so any change counts as ticking.
+        // So does the area past the edge changing, as the cells along it will change with it.
+        let ticked = !changes.is_empty() || background != self.background;
+        // Every cell along the edge has a new neighbor when the background changes, so they all have to be checked.
+        self.active =
+            (background == self.background).then(|| changes.into_iter().map(|(i, _)| i).collect());
+        self.background = background;
+        if ticked {
+            self.generation += 1;
+        }
+
+        Ok(ticked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+
+    const BLINKER: [(usize, usize); 3] = [(1, 2), (2, 2), (3, 2)];
+    const BLOCK: [(usize, usize); 4] = [(1, 1), (2, 1), (1, 2), (2, 2)];
+    const GLIDER: [(usize, usize); 5] = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+    const R_PENTOMINO: [(usize, usize); 5] = [(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)];
+
+    /// Brings ``cells`` to life on ``conway``.
+    fn with_cells(mut conway: Conway, cells: &[(usize, usize)]) -> Conway {
+        for &(x, y) in cells {
+            conway.revive_cell(x, y).unwrap();
+        }
+        conway
+    }
+
+    /// Builds a board with only ``cells`` alive.
+    fn board(width: usize, height: usize, cells: &[(usize, usize)]) -> Conway {
+        with_cells(
+            ConwayBuilder::new().size(width, height).seed(0).build(),
+            cells,
+        )
+    }
+
+    #[test]
+    fn run_headless_stops_on_still_lifes() {
+        let summary = board(4, 4, &BLOCK).run_headless(None, None).unwrap();
+        assert_eq!(summary.generations, 0);
+        assert_eq!(summary.population, 4);
+        assert_eq!(summary.end, Some(RunEnd::Stable));
+
+        // Three cells of a block fill in the fourth, and then it stops.
+        let summary = board(4, 4, &BLOCK[..3]).run_headless(None, None).unwrap();
+        assert_eq!(summary.generations, 1);
+        assert_eq!(summary.population, 4);
+        assert_eq!((summary.births, summary.deaths), (1, 0));
+        assert_eq!(summary.end, Some(RunEnd::Stable));
+    }
+
+    #[test]
+    fn run_headless_counts_dying_out() {
+        let summary = board(5, 5, &BLINKER[..2]).run_headless(None, None).unwrap();
+        assert_eq!(summary.generations, 1);
+        assert_eq!(summary.population, 0);
+        assert_eq!(summary.peak_population, 2);
+        assert_eq!((summary.births, summary.deaths), (0, 2));
+        assert_eq!(summary.end, Some(RunEnd::Stable));
+    }
+
+    #[test]
+    fn run_headless_stops_at_the_generation_cap() {
+        let summary = board(5, 5, &BLINKER).run_headless(Some(10), None).unwrap();
+        assert_eq!(summary.generations, 10);
+        assert_eq!(summary.population, 3);
+        assert_eq!((summary.births, summary.deaths), (20, 20));
+        assert_eq!(summary.end, Some(RunEnd::Capped));
+
+        // A glider keeps its 5 cells as it goes around a wrapped board.
+        let mut conway = with_cells(ConwayBuilder::new().size(8, 8).wrap(true).build(), &GLIDER);
+        let summary = conway.run_headless(Some(32), None).unwrap();
+        assert_eq!(summary.generations, 32);
+        assert_eq!(summary.population, 5);
+        assert_eq!(summary.end, Some(RunEnd::Capped));
+    }
+
+    #[test]
+    fn run_headless_stops_at_the_population_target() {
+        // The R-pentomino goes 5, 6, 7, 9, 8, 9, 12 cells.
+        let mut conway = board(20, 20, &[]);
+        conway.place(&R_PENTOMINO, (9, 9)).unwrap();
+        let summary = conway
+            .run_headless(None, Some(PopulationTarget::AtLeast(10)))
+            .unwrap();
+        assert_eq!(summary.generations, 6);
+        assert_eq!(summary.population, 12);
+        assert_eq!(summary.end, Some(RunEnd::TargetReached));
+
+        // A board that starts on the target doesn't tick at all.
+        let summary = board(5, 5, &BLINKER)
+            .run_headless(None, Some(PopulationTarget::Below(4)))
+            .unwrap();
+        assert_eq!(summary.generations, 0);
+        assert_eq!(summary.end, Some(RunEnd::TargetReached));
+    }
+
+    #[test]
+    fn peaks_are_tracked_across_a_run() {
+        // A glider has 5 cells in every phase, so its peak is where it started.
+        let mut glider = with_cells(ConwayBuilder::new().size(8, 8).wrap(true).build(), &GLIDER);
+        let summary = glider.run_headless(Some(20), None).unwrap();
+        assert_eq!(summary.peak_population, 5);
+        assert_eq!(summary.peak_generation, 0);
+
+        // The R-pentomino goes 5, 6, 7, 9, 8, 9, 12, 11, 18, 11 cells.
+        let mut methuselah = board(20, 20, &[]);
+        methuselah.place(&R_PENTOMINO, (9, 9)).unwrap();
+        let mut summary = methuselah.run_headless(Some(8), None).unwrap();
+        assert_eq!(summary.peak_population, 18);
+        assert_eq!(summary.peak_generation, 8);
+        // Dropping back down afterwards leaves the peak where it was.
+        assert!(methuselah.advance(&mut summary, None, None).unwrap());
+        assert_eq!(summary.population, 11);
+        assert_eq!((summary.peak_population, summary.peak_generation), (18, 8));
+    }
+
+    #[test]
+    fn active_ticks_match_full_scans() {
+        let mut rng = StdRng::seed_from_u64(386);
+        let rules: [Rule; 4] = [
+            Rule::LIFE,
+            Rule::BRIANS_BRAIN,
+            // B0 rules flip the area past the edge, which makes every edge cell change.
+            "B0/S8".parse().unwrap(),
+            "B36/S23".parse().unwrap(),
+        ];
+        // A lopsided neighborhood, where cells don't count each other back.
+        let knight = vec![(1, 2), (2, -1), (-1, -2), (0, 1)];
+        for board in 0..300 {
+            let (custom, hex, wrap) = (board % 4 == 2, board % 4 == 3, board / 4 % 2 == 1);
+            let rule = if hex {
+                Rule::HEX_LIFE
+            } else {
+                rules[board / 8 % rules.len()]
+            };
+            let (width, height) = (rng.gen_range(1..12), rng.gen_range(1..12));
+            let mut builder = ConwayBuilder::new()
+                .size(width, height)
+                .seed(rng.gen())
+                .wrap(wrap)
+                .hex(hex)
+                .rule(rule);
+            if custom {
+                builder = builder.neighborhood(knight.clone());
+            }
+            let mut conway = builder.build();
+            conway.generate_density_board(rng.gen_range(0.1..0.6));
+            let mut expected = Vec::new();
+            for generation in 0..20 {
+                let changed = conway.tick_into(&mut expected);
+                assert_eq!(conway.tick().unwrap(), changed);
+                let cells: Vec<CellState> = conway.rows().flatten().collect();
+                assert_eq!(
+                    cells, expected,
+                    "{width}x{height} custom={custom} hex={hex} wrap={wrap} rule={rule} generation {generation}"
+                );
+                // Cells changed between ticks have to be rechecked too.
+                if rng.gen_bool(0.2) {
+                    let (x, y) = (rng.gen_range(0..width), rng.gen_range(0..height));
+                    conway.revive_cell(x, y).unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wrapped_hex_boards_with_odd_heights_tick_like_full_scans() {
+        // Rows 0 and 14 are both even, so the top and bottom edges meet unevenly.
+        let mut conway = with_cells(
+            ConwayBuilder::new()
+                .size(15, 15)
+                .wrap(true)
+                .hex(true)
+                .rule("B1/S".parse().unwrap())
+                .build(),
+            &[(4, 0)],
+        );
+        let mut expected = Vec::new();
+        for generation in 0..8 {
+            let changed = conway.tick_into(&mut expected);
+            assert_eq!(conway.tick().unwrap(), changed);
+            let cells: Vec<CellState> = conway.rows().flatten().collect();
+            assert_eq!(cells, expected, "generation {generation}");
+        }
+    }
+
+    #[test]
+    fn life_ticks_the_same_when_not_totalistic() {
+        let random_board = |builder: ConwayBuilder| {
+            let mut conway = builder.size(16, 16).seed(391).build();
+            conway.generate_random_board();
+            conway
+        };
+        // Counting an alive cell itself moves its survival counts up by one, so B3/S34 is Life again.
+        let shifted: Rule = "B3/S34".parse().unwrap();
+        let mut boards = [
+            random_board(ConwayBuilder::new()),
+            random_board(ConwayBuilder::new().totalistic(false)),
+            random_board(ConwayBuilder::new().rule(Rule::LIFE.with_totalistic(false))),
+            random_board(ConwayBuilder::new().rule(shifted).totalistic(true)),
+        ];
+        for _ in 0..30 {
+            for conway in &mut boards {
+                conway.tick().unwrap();
+            }
+            for conway in &boards[1..] {
+                assert_eq!(conway.cells, boards[0].cells);
+            }
+        }
+    }
+
+    #[test]
+    fn symmetric_board_on_an_empty_board_does_nothing() {
+        for (width, height) in [(0, 5), (5, 0), (0, 0)] {
+            let mut conway = ConwayBuilder::new().size(width, height).seed(1).build();
+            for symmetry in [Symmetry::Horizontal, Symmetry::Vertical, Symmetry::Quadrant] {
+                assert!(conway.generate_symmetric_board(symmetry).is_ok());
+            }
+            assert_eq!(conway.population(), 0);
+        }
+    }
+
+    #[test]
+    fn totalistic_rules_are_written_as_the_rule_that_ticks_the_same() {
+        let rule = "B3/S3459".parse::<Rule>().unwrap().with_totalistic(true);
+        assert_eq!(rule.to_string(), "B3/S2348");
+        assert_eq!("B3/S9".parse::<Rule>().unwrap().to_string(), "B3/S9");
+        assert!("B9/S23".parse::<Rule>().is_err());
+
+        let mut conway = ConwayBuilder::new()
+            .size(16, 16)
+            .seed(391)
+            .rule(rule)
+            .build();
+        conway.generate_random_board();
+        let replay = parse_replay(&conway.to_replay(None)).unwrap();
+        let mut replayed = with_cells(
+            ConwayBuilder::new()
+                .size(16, 16)
+                .rule(replay.pattern.rule)
+                .build(),
+            &replay.pattern.cells,
+        );
+        for _ in 0..30 {
+            conway.tick().unwrap();
+            replayed.tick().unwrap();
+            assert_eq!(replayed.cells, conway.cells);
+        }
+    }
+
+    #[test]
+    fn generation_counts_ticks_that_change_the_board() {
+        // A blinker changes every tick.
+        let mut conway = board(5, 5, &BLINKER);
+        for generation in 1..=4 {
+            assert!(conway.tick().unwrap());
+            assert_eq!(conway.generation(), generation);
+        }
+        conway.clear();
+        assert_eq!(conway.generation(), 0);
+
+        // A block never changes, so the generation stays put.
+        let mut conway = board(4, 4, &BLOCK);
+        for _ in 0..3 {
+            assert!(!conway.tick().unwrap());
+            assert_eq!(conway.generation(), 0);
+        }
+    }
+
+    #[test]
+    fn clear_resets_the_board_to_as_if_it_was_just_built() {
+        let mut conway = board(5, 5, &BLINKER);
+        conway.tick().unwrap();
+        conway.tick().unwrap();
+        assert!(conway.active.is_some());
+        assert_eq!((conway.births(), conway.deaths()), (2, 2));
+        conway.clear();
+        assert_eq!(conway.population(), 0);
+        assert_eq!(conway.generation(), 0);
+        assert_eq!((conway.births(), conway.deaths()), (0, 0));
+        assert_eq!((conway.edge_changes(), conway.changed_cells()), (0, 0));
+        assert!(conway.active.is_none());
+
+        // B0 without S8 makes the area past the edge flash on and off every tick.
+        let flashing: Rule = "B0/S".parse().unwrap();
+        let built = || ConwayBuilder::new().size(4, 4).rule(flashing).build();
+        let mut conway = built();
+        conway.tick().unwrap();
+        assert_eq!(conway.background, CellState::Alive);
+        conway.clear();
+        assert_eq!(conway.background, CellState::Dead);
+        let mut fresh = built();
+        conway.tick().unwrap();
+        fresh.tick().unwrap();
+        assert_eq!(conway.cells, fresh.cells);
+    }
+
+    #[test]
+    fn wrapped_corners_see_each_other() {
+        let corners = [(0, 0), (4, 0), (0, 3), (4, 3)];
+        let wrapped = with_cells(ConwayBuilder::new().size(5, 4).wrap(true).build(), &corners);
+        let bounded = board(5, 4, &corners);
+        for (x, y) in corners {
+            assert_eq!(wrapped.neighbors(x, y).unwrap(), 3);
+            assert_eq!(bounded.neighbors(x, y).unwrap(), 0);
+        }
+        // (1, 0) sees (0, 0) on both, and (0, 3) across the top edge when wrapped.
+        assert_eq!(wrapped.neighbors(1, 0).unwrap(), 2);
+        assert_eq!(bounded.neighbors(1, 0).unwrap(), 1);
+        // (0, 1) sees (0, 0) on both, and (4, 0) across the left edge when wrapped.
+        assert_eq!(wrapped.neighbors(0, 1).unwrap(), 2);
+        assert_eq!(bounded.neighbors(0, 1).unwrap(), 1);
+        assert_eq!(wrapped.neighbors(2, 1).unwrap(), 0);
+    }
+
+    #[test]
+    fn wrapped_edges_see_the_opposite_edge() {
+        let cells = [(1, 3), (2, 3), (3, 3), (4, 1), (4, 2)];
+        let wrapped = with_cells(ConwayBuilder::new().size(5, 4).wrap(true).build(), &cells);
+        let bounded = board(5, 4, &cells);
+        // The top row sees the bottom row, and the left column sees the right one.
+        let expected = [
+            ((0, 0), 2, 0),
+            ((1, 0), 2, 0),
+            ((2, 0), 3, 0),
+            ((3, 0), 3, 1),
+            ((0, 1), 2, 0),
+            ((0, 2), 3, 1),
+            ((3, 2), 4, 4),
+        ];
+        for ((x, y), wrapped_count, bounded_count) in expected {
+            assert_eq!(wrapped.neighbors(x, y).unwrap(), wrapped_count, "{x},{y}");
+            assert_eq!(bounded.neighbors(x, y).unwrap(), bounded_count, "{x},{y}");
+        }
+    }
+
+    #[test]
+    fn seeded_boards_generate_the_same_cells() {
+        let seeded = |seed| ConwayBuilder::new().size(16, 12).seed(seed).build();
+        let (mut first, mut second, mut other) = (seeded(329), seeded(329), seeded(330));
+        for conway in [&mut first, &mut second, &mut other] {
+            conway.generate_random_board();
+        }
+        assert_eq!(first.cells, second.cells);
+        assert_ne!(first.cells, other.cells);
+
+        let (mut first, mut second, mut other) = (seeded(329), seeded(329), seeded(330));
+        for conway in [&mut first, &mut second, &mut other] {
+            conway.generate_board(40).unwrap();
+        }
+        assert_eq!(first.population(), 40);
+        assert_eq!(first.cells, second.cells);
+        assert_ne!(first.cells, other.cells);
+    }
+
+    #[test]
+    fn single_cell_board_ticks_and_exports() {
+        let mut conway = board(1, 1, &[(0, 0)]);
+        assert_eq!(conway.neighbors(0, 0).unwrap(), 0);
+        assert_eq!(conway.render_string(), "O\n");
+        let pattern = parse_rle(&conway.to_rle_full()).unwrap();
+        assert_eq!((pattern.width, pattern.height), (1, 1));
+        assert_eq!(pattern.cells, [(0, 0)]);
+
+        assert!(conway.tick().unwrap());
+        assert_eq!(conway.population(), 0);
+        assert_eq!(conway.render_string(), ".\n");
+        assert!(!conway.tick().unwrap());
+    }
+
+    #[test]
+    fn thin_boards_tick_along_their_only_row_or_column() {
+        // A blinker lying along the board can't turn, so its ends die and then so does its middle.
+        for (width, height, cells) in [
+            (5, 1, [(1, 0), (2, 0), (3, 0)]),
+            (1, 5, [(0, 1), (0, 2), (0, 3)]),
+        ] {
+            let mut conway = board(width, height, &cells);
+            assert_eq!(conway.neighbors(cells[1].0, cells[1].1).unwrap(), 2);
+            assert!(conway.tick().unwrap());
+            assert_eq!(conway.population(), 1);
+            assert_eq!(conway.neighbors(cells[1].0, cells[1].1).unwrap(), 0);
+            assert!(conway.tick().unwrap());
+            assert_eq!(conway.population(), 0);
+            assert!(!conway.tick().unwrap());
+
+            let pattern = parse_rle(&board(width, height, &cells).to_rle_full()).unwrap();
+            assert_eq!((pattern.width, pattern.height), (width, height));
+            assert_eq!(pattern.cells, cells);
+        }
+    }
+
+    #[test]
+    fn thin_boards_wrap() {
+        for (width, height) in [(1, 1), (1, 6), (6, 1)] {
+            let mut conway = ConwayBuilder::new()
+                .size(width, height)
+                .seed(351)
+                .wrap(true)
+                .build();
+            conway.generate_random_board();
+            let mut expected = Vec::new();
+            for _ in 0..5 {
+                let changed = conway.tick_into(&mut expected);
+                assert_eq!(conway.tick().unwrap(), changed);
+                assert_eq!(conway.rows().flatten().collect::<Vec<_>>(), expected);
+            }
+            assert_eq!(conway.render_string().lines().count(), height);
+        }
+    }
+
+    #[test]
+    fn generating_a_board_resets_the_generation() {
+        let generators: [fn(&mut Conway); 5] = [
+            |conway| conway.generate_board(10).unwrap(),
+            Conway::generate_random_board,
+            |conway| conway.generate_density_board(0.3),
+            |conway| conway.generate_clustered_board(2, 3),
+            |conway| conway.generate_symmetric_board(Symmetry::Quadrant).unwrap(),
+        ];
+        for generate in generators {
+            let mut conway = board(8, 8, &BLINKER);
+            conway.tick().unwrap();
+            conway.tick().unwrap();
+            assert_eq!(conway.generation(), 2);
+            generate(&mut conway);
+            assert_eq!(conway.generation(), 0);
+        }
+    }
+
+    #[test]
+    fn render_ansi_string_draws_each_row() {
+        let conway = board(3, 2, &[(0, 0), (2, 1)]);
+        let alive = "\x1B[38;5;10m█\x1B[39m";
+        let options = RenderOptions {
+            offset: 1,
+            dead_char: '.',
+            ..RenderOptions::default()
+        };
+        assert_eq!(
+            conway.render_ansi_string(&options),
+            format!(" {alive}..\x1B[0m\r\n ..{alive}\x1B[0m\r\n")
+        );
+    }
+
+    #[test]
+    fn render_ansi_string_labels_the_grid() {
+        let conway = board(2, 1, &[]);
+        let options = RenderOptions {
+            grid: true,
+            ..RenderOptions::default()
+        };
+        assert_eq!(
+            conway.render_ansi_string(&options),
+            "  \x1B[38;5;8m1 \x1B[39m\r\n\x1B[38;5;8m1 \x1B[39m  \x1B[0m\r\n"
+        );
+    }
+
+    #[test]
+    fn rules_with_the_most_dying_states_round_trip() {
+        for states in [252, 253, 254, u8::MAX] {
+            let rule = Rule::LIFE.with_dying_states(states);
+            assert_eq!(rule.dying_states(), states.min(253));
+            assert_eq!(rule.to_string().parse::<Rule>().unwrap(), rule);
+        }
+    }
+
+    #[test]
+    fn patterns_placed_past_the_end_of_usize_are_too_big() {
+        let mut conway = board(4, 4, &[]);
+        for at in [
+            (usize::MAX, 0),
+            (0, usize::MAX),
+            (usize::MAX - 1, usize::MAX - 1),
+        ] {
+            assert!(matches!(
+                conway.place(&[(1, 0), (0, 1)], at),
+                Err(ConwayError::PatternTooBig { .. })
+            ));
+        }
+        assert_eq!(conway.population(), 0);
+    }
+
+    #[test]
+    fn live_neighbors_stop_at_255() {
+        let row: Vec<(usize, usize)> = (0..301).map(|x| (x, 0)).collect();
+        let conway = with_cells(
+            ConwayBuilder::new()
+                .size(301, 1)
+                .neighborhood((1..=300).map(|x| (x, 0)).collect())
+                .build(),
+            &row,
+        );
+        assert_eq!(conway.neighbors(0, 0).unwrap(), 300);
+        assert_eq!(conway.live_neighbors(0, 0), Some(u8::MAX));
+        assert_eq!(conway.live_neighbors(100, 0), Some(200));
+    }
+
+    #[test]
+    fn coordinate_pairs_parse() {
+        assert_eq!(parse_coordinate_pair("1,2").unwrap(), (1, 2));
+        assert_eq!(parse_coordinate_pair("0,0").unwrap(), (0, 0));
+        assert_eq!(parse_coordinate_pair("-3,+4").unwrap(), (-3, 4));
+        assert_eq!(
+            parse_coordinate_pair(&format!("{},{}", isize::MIN, isize::MAX)).unwrap(),
+            (isize::MIN, isize::MAX)
+        );
+    }
+
+    #[test]
+    fn malformed_coordinate_pairs_are_rejected() {
+        let too_big = format!("{}0,1", isize::MAX);
+        for input in ["1,2,3", "a,b", ",,", "", "1", ",1", "1,", "1.5,2", &too_big] {
+            assert!(parse_coordinate_pair(input).is_err(), "{input:?}");
+        }
+    }
+}
@@ -2,19 +2,45 @@
 #![allow(
     clippy::cast_possible_truncation,
     clippy::cast_possible_wrap,
+    clippy::cast_precision_loss,
     clippy::cast_sign_loss
 )]
 
 use core::fmt;
-use std::{io, process::exit, sync::OnceLock, thread, time::Duration, vec};
+use std::{
+    array,
+    collections::{HashMap, HashSet, VecDeque},
+    ffi::OsStr,
+    fmt::Write as _,
+    fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    io::{self, IsTerminal, Write},
+    path::{Path, PathBuf},
+    process::exit,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+    time::{Duration, Instant},
+    vec,
+};
+#[cfg(feature = "logging")]
+use std::{fs::File, sync::Mutex};
 
-use clap::{command, Parser, ValueEnum};
+use clap::{Parser, ValueEnum};
+use conway::{
+    parse_coordinate_pair, parse_life106, parse_plaintext, parse_replay, parse_rle, Conway,
+    ConwayBuilder, PatternFile, PopulationTarget, RenderOptions, Rule, RunEnd, RunSummary,
+    Symmetry,
+};
 use crossterm::{
-    cursor, execute,
-    style::{self, Stylize},
-    terminal,
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute,
+    style::Color,
+    terminal, Command,
 };
-use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+use unicode_width::UnicodeWidthChar;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let (w, h) = *SIZE.get_or_init(|| {
@@ -22,14 +48,101 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         (size.0 as usize, size.1 as usize)
     });
 
-    // Parse the cli before touching the terminal as we can't reset what we've done.
-    let cli = Cli::parse();
-    let width = cli.width.unwrap_or(w);
-    let height = cli.height.unwrap_or(h);
+    // Parse the cli and set up the board before touching the terminal as we can't reset what we've done.
+    // Anything printed once we're on the alternate screen is also cleared before it can be read.
+    #[cfg(not(feature = "config"))]
+    let mut cli = Cli::parse();
+    #[cfg(feature = "config")]
+    let mut cli = parse_with_config()?;
+    // Only the summary is printed when counting, so that it can be parsed.
+    cli.quiet |= cli.count_only;
+    if cli.list_patterns {
+        return Ok(list_patterns(&cli)?);
+    }
+    // A pattern from the library loads the same as one given with --file.
+    if let (Some(dir), Some(name)) = (&cli.pattern_lib, &cli.pattern_name) {
+        cli.file = Some(find_library_pattern(dir, name)?);
+    }
+    // Hex cells are drawn two characters wide, with odd rows a character further right.
+    let width = cli
+        .width
+        .unwrap_or_else(|| w.saturating_sub(usize::from(cli.hex)) / cell_width(cli.hex));
+    // A wrapped hex grid needs an even height, so one that fills the terminal leaves off a row if it has to.
+    let height = cli
+        .height
+        .unwrap_or(if cli.hex && cli.wrap { h - h % 2 } else { h });
+
+    let capacity = width
+        .checked_mul(height)
+        .ok_or_else(|| format!("A {width}x{height} board has too many cells to build."))?;
+    if let Some(num_cells) = cli.num_cells {
+        if num_cells > capacity {
+            eprintln!(
+                "Warning: The {width}x{height} board only has room for {capacity} cells, not {num_cells}, so it'll be filled."
+            );
+            cli.num_cells = Some(capacity);
+        }
+    }
+
+    #[cfg(feature = "logging")]
+    if let Some(path) = cli.log.as_ref().filter(|_| !cli.dry_run) {
+        init_logger(path)?;
+    }
+
+    let mut builder = ConwayBuilder::new()
+        .size(width, height)
+        .wrap(cli.wrap)
+        .hex(cli.hex)
+        .rule(board_rule(&cli));
+    if let Some(states) = cli.generations {
+        builder = builder.dying_states(states);
+    }
+    // Rerunning a recording needs its seed, so one is picked when none was given.
+    let seed = cli.seed.or_else(|| cli.record.is_some().then(rand::random));
+    if let Some(seed) = seed {
+        builder = builder.seed(seed);
+    }
+
+    let mut conway = build_board(&cli, builder)?;
+    if cli.dry_run {
+        print_settings(&conway, &cli, seed);
+        return Ok(());
+    }
+    if let Some(path) = &cli.record {
+        fs::write(path, conway.to_replay(seed))
+            .map_err(|e| format!("Unable to write the recording to {}: {e}", path.display()))?;
+    }
+    if let Some(path) = &cli.save_bin {
+        fs::write(path, conway.to_bytes())
+            .map_err(|e| format!("Unable to write the board to {}: {e}", path.display()))?;
+    }
+
+    if !confirm_size(&conway, &cli, (w, h))? {
+        return Ok(());
+    }
+
+    // Skip ahead while ctrl-c still just stops the program, as nothing about the terminal needs putting back yet.
+    let mut skipped = 0;
+    if cli.skip > 0 {
+        if !cli.quiet {
+            println!("Skipping the first {} generations...", cli.skip);
+        }
+        // Skipping past the generation cap would leave nothing to show.
+        let skip = match cli.max_gen {
+            0 => cli.skip,
+            max_gen => cli.skip.min(max_gen),
+        };
+        skipped = conway.run_headless(Some(skip), None)?.generations;
+    }
+
+    if cli.count_only {
+        return Ok(count_only(&mut conway, &cli, skipped)?);
+    }
 
     execute!(io::stdout(), terminal::EnterAlternateScreen)
         .map_err(|_| "Unable to enter alternative screen.")?;
     ctrlc::set_handler(|| {
+        terminal::disable_raw_mode().expect("Unable to disable raw mode.");
         execute!(io::stdout(), terminal::LeaveAlternateScreen)
             .expect("Unable to leave alternate screen.");
         execute!(io::stdout(), cursor::Show).expect("Unable to show cursor.");
@@ -37,85 +150,566 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     })
     .map_err(|_| "Unable to register ctrl-c handler.")?;
     execute!(io::stdout(), cursor::Hide).map_err(|_| "Unable to hide cursor.")?;
+    // Raw mode lets us read keys as they're pressed, but it also means ctrl-c has to be handled as a key.
+    terminal::enable_raw_mode().map_err(|_| "Unable to enable raw mode.")?;
 
-    if w < width || h < height {
-        println!("Warning: Your terminal is not big enough for the size of this board.");
-        println!("Your board is {width}x{height} but your terminal is only {w}x{h}");
-        let mut buffer = String::new();
-        println!("Press any button to continue: ");
-        std::io::stdin()
-            .read_line(&mut buffer)
-            .expect("Unable to get stdin.");
-    }
-
-    let rng = if cli.seed.is_some() {
-        StdRng::seed_from_u64(cli.seed.unwrap())
-    } else {
-        StdRng::from_rng(thread_rng()).expect("RNG generation managed to fail?")
-    };
-
-    let mut conway;
-    if let Some(pattern) = cli.pattern {
-        println!("Found a pattern argument, using it. ({pattern})");
-        let (x, y) = pattern.size();
-        conway = Conway::new(x, y, rng);
-        for (coord_x, coord_y) in pattern.coordinates() {
-            conway.revive_cell(coord_x, coord_y)?;
-        }
-    } else {
-        conway = Conway::new(width, height, rng);
-
-        if let Some(cells) = cli.cells {
-            println!(
-                "Found cells as an argument, using them instead of RNG. (total: {})",
-                cells.len()
-            );
-            for cell in cells {
-                let (x, y) = cell;
-                conway.revive_cell(x - 1, y - 1)?;
-            }
-        } else {
-            match cli.num_cells {
-                Some(n) => conway.generate_board(n)?,
-                None => conway.generate_random_board(),
-            }
-        }
-    }
-
-    conway.game_loop()?;
+    // Put the terminal back to how we found it even if the game loop failed.
+    let result = game_loop(&mut conway, &cli, skipped);
+    terminal::disable_raw_mode().map_err(|_| "Unable to disable raw mode.")?;
     execute!(io::stdout(), terminal::LeaveAlternateScreen)
         .map_err(|_| "Unable to exit alternative screen.")?;
     execute!(io::stdout(), cursor::Show).map_err(|_| "Unable to show cursor again.")?;
 
-    Ok(())
+    if result.is_ok() && cli.print_final {
+        print!("{}", conway.render_string());
+    }
+    Ok(result?)
+}
+
+/// Warns when the board is too big for the terminal, and asks whether to run it anyway.
+/// This will return ``false`` if the user asked to quit, else it will return ``true``.
+fn confirm_size(conway: &Conway, cli: &Cli, (w, h): (usize, usize)) -> Result<bool, String> {
+    // Patterns and images bring their own size, so check the board that was actually made.
+    let (width, height) = (conway.width(), conway.height());
+    // Nothing is drawn when only counting, so the terminal can be any size.
+    if (w >= drawn_width(conway.is_hex(), width) && h >= height) || cli.force || cli.count_only {
+        return Ok(true);
+    }
+    println!("Warning: Your terminal is not big enough for the size of this board.");
+    println!("Your board is {width}x{height} but your terminal is only {w}x{h}");
+    // Nobody is around to press anything when stdin isn't a terminal, so don't wait on it.
+    if !io::stdin().is_terminal() {
+        return Ok(true);
+    }
+    println!("Press any button to continue, or q to quit: ");
+    terminal::enable_raw_mode().map_err(|_| "Unable to enable raw mode.")?;
+    let key = read_key();
+    terminal::disable_raw_mode().map_err(|_| "Unable to disable raw mode.")?;
+    key
 }
 
 static SIZE: OnceLock<(usize, usize)> = OnceLock::new();
 
+/// Whether the status line is drawn, which pressing h toggles while the board runs.
+static SHOW_STATUS: AtomicBool = AtomicBool::new(true);
+
+/// Whether r was pressed, asking for the live cells to be moved to the middle of the board before the next generation.
+static RECENTER: AtomicBool = AtomicBool::new(false);
+
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
+#[allow(clippy::struct_excessive_bools)]
 struct Cli {
+    #[arg(value_parser = parse_dimension)]
     /// The width of the Conway board.
     width: Option<usize>,
 
+    #[arg(value_parser = parse_dimension)]
     /// The height of the Conway board.
     height: Option<usize>,
 
     #[arg(short, long, conflicts_with_all = ["pattern", "num_cells", "seed"], value_parser = parse_coordinate_pair, num_args=0..)]
     /// A space seperated set of coordinate pairs in the form x,y
-    cells: Option<Vec<(usize, usize)>>,
+    cells: Option<Vec<(isize, isize)>>,
+
+    #[arg(long, value_name = "X,Y", conflicts_with_all = ["pattern", "cells", "file", "pattern_name", "replay"], value_parser = parse_coordinate_pair, num_args = 1..)]
+    /// A space separated set of coordinate pairs to keep dead once the random cells are generated, such as a moat
+    /// around the edge. They're measured from --origin the same as --cells.
+    dead: Option<Vec<(isize, isize)>>,
+
+    #[arg(long, value_enum, default_value_t = Origin::TopLeft)]
+    /// Where --cells coordinates are measured from. From the top left they start at 1,1, from the center they start at 0,0 and can be negative.
+    /// A pair starting with a minus has to be attached with an equals sign, like --cells=-1,0.
+    origin: Origin,
+
+    #[arg(long, value_name = "POSITION", value_enum, default_value_t = Origin::TopLeft)]
+    /// Where to put a --pattern or --file pattern on the board. In the center, the pattern is placed on a board
+    /// the size of the terminal, or of the width and height if they're given, instead of one the size of the pattern.
+    at: Origin,
 
     #[arg(short, long, conflicts_with_all=["pattern", "cells"])]
     /// The number of cells to generate. If not provided, the default is a 50% chance per cell.
     num_cells: Option<usize>,
 
+    #[arg(long, conflicts_with_all = ["pattern", "cells", "num_cells"])]
+    /// Generate the random cells in a few blobs instead of evenly across the board.
+    clustered: bool,
+
+    #[arg(long, value_name = "MODE", conflicts_with_all = ["pattern", "cells", "num_cells", "clustered"])]
+    /// Generate the random cells so that the board is symmetric.
+    symmetric: Option<SymmetryMode>,
+
     #[arg(short, long, conflicts_with_all = ["cells", "num_cells", "seed"])]
     /// The pattern to use.
     pattern: Option<Pattern>,
 
+    #[arg(short, long, value_name = "PATH", conflicts_with_all = ["pattern", "cells", "num_cells", "clustered", "symmetric", "seed"])]
+    /// A pattern file to load the board from, either RLE (.rle), plaintext (.cells) or Life 1.06 (.lif, .life).
+    file: Option<PathBuf>,
+
+    #[arg(long, value_name = "DIR")]
+    /// A directory of .rle and .cells pattern files to pick from by name with --pattern-name.
+    pattern_lib: Option<PathBuf>,
+
+    #[arg(long, value_name = "NAME", requires = "pattern_lib", conflicts_with_all = ["pattern", "cells", "num_cells", "clustered", "symmetric", "seed", "file"])]
+    /// The pattern in --pattern-lib to load the board from, named after its file without the extension.
+    pattern_name: Option<String>,
+
+    #[arg(long)]
+    /// List the built-in patterns, and the patterns in --pattern-lib if one is given, then exit.
+    list_patterns: bool,
+
+    #[cfg(feature = "image")]
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["pattern", "cells", "num_cells", "clustered", "symmetric", "file", "pattern_name", "seed", "dead", "load_bin"])]
+    /// A PNG to seed the board from, where dark pixels are alive. The board is the size of the image, unless a width and height are given to scale it to.
+    image: Option<PathBuf>,
+
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["width", "height", "pattern", "cells", "num_cells", "clustered", "symmetric", "file", "pattern_name", "seed", "wrap", "hex", "automaton", "generations", "record"])]
+    /// Start from a board saved with --record, with the same size, rule, cells, seed and grid.
+    replay: Option<PathBuf>,
+
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["width", "height", "pattern", "cells", "num_cells", "clustered", "symmetric", "file", "pattern_name", "replay", "dead"])]
+    /// Start from a board saved with --save-bin, which has the size and cells of the board but not its rule or seed.
+    load_bin: Option<PathBuf>,
+
+    #[arg(long, value_name = "PATH")]
+    /// Save the starting board to a compact binary file, which is much smaller and quicker to load than text for big boards.
+    save_bin: Option<PathBuf>,
+
+    #[arg(long, value_name = "PATH")]
+    /// Save the starting board to a file along with everything needed to rerun it exactly, to load again with --replay.
+    /// A seed is picked if none was given, so random boards can be rerun too.
+    record: Option<PathBuf>,
+
     #[arg(short, long, conflicts_with_all = ["cells", "pattern"])]
-    /// The seed to use for generation of the initial random cells. This can only be used with num_cells.
+    /// The seed to use for generation of the initial random cells. The same seed and size always generate the same board.
     seed: Option<u64>,
+
+    #[cfg(feature = "config")]
+    #[arg(long, value_name = "PATH")]
+    /// A TOML file to read default options from, in place of conway/config.toml in the user's config directory.
+    /// Options given on the command line override the file.
+    config: Option<PathBuf>,
+
+    #[cfg(feature = "logging")]
+    #[arg(long, value_name = "PATH")]
+    /// A file to log the population, births and deaths of every generation to.
+    log: Option<PathBuf>,
+
+    #[arg(long, value_enum)]
+    /// The automaton to run, which defaults to life, or hex-life on a hex grid.
+    /// A pattern file's rule is used over this one.
+    automaton: Option<Automaton>,
+
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u8).range(1..=253))]
+    /// Have cells decay for N generations after dying before they're dead, like a Generations rule.
+    /// Decaying cells don't count as live neighbors.
+    generations: Option<u8>,
+
+    #[arg(short, long)]
+    /// Wrap the edges of the board around to the opposite side.
+    wrap: bool,
+
+    #[arg(long, conflicts_with = "grid")]
+    /// Use a hex grid, where every cell has six neighbors and odd rows are drawn half a cell to the right.
+    /// A wrapped hex grid needs an even height for its top and bottom rows to line up, so an odd one is refused.
+    hex: bool,
+
+    #[arg(long)]
+    /// Start without warning that the board is bigger than the terminal.
+    force: bool,
+
+    #[arg(long)]
+    /// Start over with a fresh random board whenever the board stops changing, instead of exiting.
+    loop_on_stable: bool,
+
+    #[arg(long)]
+    /// Stop once the board starts repeating itself, including spaceships coming back around a wrapped board.
+    stop_on_cycle: bool,
+
+    #[arg(long)]
+    /// Stop once all that's left on the board is still lifes and oscillators that flip back every other generation,
+    /// like blocks and blinkers, however many of them there are.
+    stop_on_oscillators: bool,
+
+    #[arg(long, conflicts_with_all = ["loop_on_stable", "screensaver"])]
+    /// When the board dies out, play it back in reverse and then forwards again, over and over, making a loop.
+    palindrome: bool,
+
+    #[arg(long)]
+    /// Keep the board interesting by starting over whenever it stops changing, starts blinking or dies down.
+    screensaver: bool,
+
+    #[arg(long, default_value_t = 10, requires = "screensaver")]
+    /// The population below which the screensaver starts over with a fresh board.
+    min_population: usize,
+
+    #[arg(long, value_name = "N", default_value_t = 100_000)]
+    /// Stop after this many generations, so a board that never settles doesn't run forever. 0 runs without a cap.
+    max_gen: u64,
+
+    #[arg(long, value_name = "N")]
+    /// Stop once the population first drops below N, or reaches N with --pop-direction above.
+    stop_at_pop: Option<usize>,
+
+    #[arg(long, value_name = "DIRECTION", value_enum, default_value_t = PopDirection::Below, requires = "stop_at_pop")]
+    /// Which way the population has to cross --stop-at-pop for the game to stop.
+    pop_direction: PopDirection,
+
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    /// Run the first N generations straight away without drawing them, to jump ahead to the interesting part.
+    skip: u64,
+
+    #[arg(long, value_name = "MS", default_value_t = 500)]
+    /// How long to show each generation for, in milliseconds.
+    delay: u64,
+
+    #[arg(long, value_name = "N", conflicts_with = "delay", value_parser = parse_gps)]
+    /// Run N generations per second instead of waiting a set delay after each one, which can be a fraction like 0.5.
+    /// The rate that's actually being met is shown on the status line.
+    gps: Option<f64>,
+
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    /// Start slowly and speed up over the first N generations, until each one takes as long as --delay or --gps asks for.
+    ramp: Option<u64>,
+
+    #[arg(long, value_name = "MS", default_value_t = 1000, requires = "ramp")]
+    /// How long the first generation of --ramp is shown for, in milliseconds.
+    /// Ramps never start faster than the delay they end at.
+    ramp_from: u64,
+
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    /// Pause after every N generations, carrying on for another N once a key is pressed.
+    auto_pause: Option<u64>,
+
+    #[arg(long)]
+    /// Show the starting board and wait for a key to be pressed before running it.
+    start_paused: bool,
+
+    #[arg(short, long)]
+    /// Only draw the board, without any messages, status line or prompt to exit once the board stops.
+    quiet: bool,
+
+    #[arg(long)]
+    /// Once the board stops, keep handling keys like c until q is pressed, instead of exiting on any key.
+    interactive_end: bool,
+
+    #[arg(long)]
+    /// Run the board without drawing it until it stops changing, starts repeating or hits --max-gen,
+    /// then print what happened as key=value lines.
+    count_only: bool,
+
+    #[arg(long)]
+    /// Print the settings the game would run with, after filling in defaults, and exit without running it.
+    dry_run: bool,
+
+    #[arg(long)]
+    /// Print the final board as plain text once the game ends, so it can be piped somewhere else.
+    /// With --count-only it's printed after the summary.
+    print_final: bool,
+
+    #[arg(long)]
+    /// Color each cell by how many live neighbors it has.
+    heatmap: bool,
+
+    #[arg(long, value_enum, default_value_t = Theme::Classic)]
+    /// The colors to draw the board with.
+    theme: Theme,
+
+    #[arg(long, value_name = "CHAR", default_value_t = ' ', value_parser = parse_dead_char)]
+    /// The character to draw dead cells with, such as · for a dotted grid. It has to take up a single column.
+    dead_char: char,
+
+    #[arg(long, value_name = "COLOR", value_parser = parse_color)]
+    /// The color to draw dead cells in, such as grey. Dead cells use the terminal's color if not provided.
+    dead_color: Option<Color>,
+
+    #[arg(long, value_name = "SIDE", value_enum, default_value_t = Anchor::Center)]
+    /// Which side of the terminal to draw the board against, when it's narrower than the terminal.
+    anchor: Anchor,
+
+    #[arg(long, value_name = "POSITION", value_enum, default_value_t = StatusPosition::Bottom)]
+    /// Where to show the status line, or off to hide it.
+    status: StatusPosition,
+
+    #[arg(long, value_name = "FORMAT")]
+    /// The text of the status line, where {generation}, {population}, {births}, {deaths}, {changes}, {period} and {rate}
+    /// are replaced by their values. {changes} is how many cells changed since the last generation.
+    status_format: Option<String>,
+
+    #[arg(long)]
+    /// Draw rulers along the top and left of the board, marking every 10th cell in the same coordinates as --cells.
+    grid: bool,
+}
+
+/// The point on the board that --cells coordinates are relative to.
+#[derive(Clone, Copy, ValueEnum)]
+enum Origin {
+    TopLeft,
+    Center,
+}
+
+/// A set of colors to draw the board with.
+#[derive(Clone, Copy, ValueEnum)]
+enum Theme {
+    /// Green cells on the terminal's background.
+    Classic,
+    /// Green cells on black.
+    Matrix,
+    /// Red and orange cells.
+    Fire,
+    /// Blue cells.
+    Ice,
+    /// White and grey cells.
+    Mono,
+}
+
+impl Theme {
+    fn alive_color(self) -> Color {
+        match self {
+            Self::Classic | Self::Matrix => Color::Green,
+            Self::Fire => Color::DarkYellow,
+            Self::Ice => Color::Cyan,
+            Self::Mono => Color::White,
+        }
+    }
+
+    /// Returns the color of cells that are decaying after dying, which is a dimmer version of the alive color.
+    fn dying_color(self) -> Color {
+        match self {
+            Self::Classic | Self::Matrix => Color::DarkGreen,
+            Self::Fire => Color::DarkRed,
+            Self::Ice => Color::DarkBlue,
+            Self::Mono => Color::DarkGrey,
+        }
+    }
+
+    /// Returns the color behind dead cells, or `None` to leave the terminal's background.
+    fn background(self) -> Option<Color> {
+        match self {
+            Self::Matrix => Some(Color::Black),
+            Self::Classic | Self::Fire | Self::Ice | Self::Mono => None,
+        }
+    }
+
+    /// Maps the amount of live neighbors a cell has to a color, going from lonely to crowded.
+    /// The gradient is smooth on terminals that can show it, and uses the nearest of the basic colors otherwise.
+    fn heat_color(self, neighbors: usize) -> Color {
+        let stops: &[(u8, u8, u8)] = match self {
+            // Lonely cells are blue, and get warmer as they get more crowded up to red.
+            Self::Classic => &[
+                (0, 0, 160),
+                (0, 200, 220),
+                (0, 200, 0),
+                (240, 220, 0),
+                (220, 0, 0),
+            ],
+            Self::Matrix => &[(0, 100, 0), (0, 255, 70), (230, 255, 230)],
+            Self::Fire => &[(120, 0, 0), (230, 40, 0), (255, 170, 0), (255, 255, 200)],
+            Self::Ice => &[(0, 0, 130), (0, 120, 230), (0, 230, 255), (240, 255, 255)],
+            Self::Mono => &[(70, 70, 70), (255, 255, 255)],
+        };
+        // Cells with 0 and 1 neighbors share a color, as do anything from 8 up.
+        let position = (neighbors.clamp(1, 8) - 1) as f64 / 7.0;
+        color_support().downsample(gradient(stops, position))
+    }
+}
+
+/// Returns the color ``position`` of the way along a gradient through ``stops``, from 0 to 1.
+fn gradient(stops: &[(u8, u8, u8)], position: f64) -> (u8, u8, u8) {
+    let scaled = position.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+    let start = (scaled.floor() as usize).min(stops.len() - 2);
+    let (from, to, along) = (stops[start], stops[start + 1], scaled - start as f64);
+    let mix = |from: u8, to: u8| {
+        (f64::from(from) + (f64::from(to) - f64::from(from)) * along).round() as u8
+    };
+    (mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+}
+
+/// The colors a terminal can show, as far as its environment says.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ColorSupport {
+    /// The 16 named colors.
+    Basic,
+    /// The 256 color palette.
+    Ansi256,
+    /// Any RGB color.
+    TrueColor,
+}
+
+/// The basic colors with roughly the RGB values terminals show them as, for finding the nearest one.
+const BASIC_COLORS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::DarkRed, (128, 0, 0)),
+    (Color::DarkGreen, (0, 128, 0)),
+    (Color::DarkYellow, (128, 128, 0)),
+    (Color::DarkBlue, (0, 0, 128)),
+    (Color::DarkMagenta, (128, 0, 128)),
+    (Color::DarkCyan, (0, 128, 128)),
+    (Color::Grey, (192, 192, 192)),
+    (Color::DarkGrey, (128, 128, 128)),
+    (Color::Red, (255, 0, 0)),
+    (Color::Green, (0, 255, 0)),
+    (Color::Yellow, (255, 255, 0)),
+    (Color::Blue, (0, 0, 255)),
+    (Color::Magenta, (255, 0, 255)),
+    (Color::Cyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// The levels each channel of the 256 color palette's 6x6x6 color cube can have.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl ColorSupport {
+    /// Works out what the terminal supports from ``COLORTERM``, then ``TERM``.
+    fn detect() -> Self {
+        let var = |name| std::env::var(name).unwrap_or_default().to_ascii_lowercase();
+        let colorterm = var("COLORTERM");
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            Self::TrueColor
+        } else if var("TERM").contains("256color") {
+            Self::Ansi256
+        } else {
+            Self::Basic
+        }
+    }
+
+    /// Returns the closest color to ``rgb`` that the terminal can show.
+    fn downsample(self, (r, g, b): (u8, u8, u8)) -> Color {
+        let distance = |(r2, g2, b2): (u8, u8, u8)| {
+            [(r, r2), (g, g2), (b, b2)]
+                .iter()
+                .map(|&(a, b)| (i32::from(a) - i32::from(b)).pow(2))
+                .sum::<i32>()
+        };
+        match self {
+            Self::TrueColor => Color::Rgb { r, g, b },
+            Self::Ansi256 => {
+                let nearest_level = |channel: u8| {
+                    (0..CUBE_LEVELS.len())
+                        .min_by_key(|&i| (i32::from(CUBE_LEVELS[i]) - i32::from(channel)).abs())
+                        .unwrap_or(0)
+                };
+                let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+                let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+                // The greyscale ramp runs from 8 to 238 in steps of 10, and can be closer for greyish colors.
+                let grey_step = ((u16::from(r) + u16::from(g) + u16::from(b)) / 3)
+                    .saturating_sub(3)
+                    .div_ceil(10)
+                    .min(23) as u8;
+                let grey = 8 + grey_step * 10;
+                if distance((grey, grey, grey)) < distance(cube) {
+                    Color::AnsiValue(232 + grey_step)
+                } else {
+                    Color::AnsiValue(16 + 36 * ri as u8 + 6 * gi as u8 + bi as u8)
+                }
+            }
+            Self::Basic => BASIC_COLORS
+                .iter()
+                .min_by_key(|(_, rgb)| distance(*rgb))
+                .map_or(Color::White, |&(color, _)| color),
+        }
+    }
+}
+
+/// Returns what colors the terminal supports, working it out the first time.
+fn color_support() -> ColorSupport {
+    static SUPPORT: OnceLock<ColorSupport> = OnceLock::new();
+    *SUPPORT.get_or_init(ColorSupport::detect)
+}
+
+/// The built-in automata, each with its own rule.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Automaton {
+    /// Conway's game of life, B3/S23.
+    Life,
+    /// Brian's Brain, where cells with exactly two live neighbors come alive and live cells always start dying.
+    BriansBrain,
+    /// A life for hex grids, B2/S34.
+    HexLife,
+}
+
+impl Automaton {
+    fn rule(self) -> Rule {
+        match self {
+            Automaton::Life => Rule::LIFE,
+            Automaton::BriansBrain => Rule::BRIANS_BRAIN,
+            Automaton::HexLife => Rule::HEX_LIFE,
+        }
+    }
+}
+
+/// The rule of the chosen automaton, which defaults to life, or hex-life on a hex grid.
+fn board_rule(cli: &Cli) -> Rule {
+    match cli.automaton {
+        Some(automaton) => automaton.rule(),
+        None if cli.hex => Rule::HEX_LIFE,
+        None => Rule::LIFE,
+    }
+}
+
+/// How many characters wide each cell is drawn.
+fn cell_width(hex: bool) -> usize {
+    if hex {
+        2
+    } else {
+        1
+    }
+}
+
+/// How many characters wide a board of the given width is drawn, including the half cell odd hex rows are shifted by.
+fn drawn_width(hex: bool, width: usize) -> usize {
+    width * cell_width(hex) + usize::from(hex)
+}
+
+/// The ways --symmetric can mirror or rotate a random board onto itself.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SymmetryMode {
+    /// Mirrored from top to bottom.
+    Horizontal,
+    /// Mirrored from left to right.
+    Vertical,
+    /// Mirrored both from top to bottom and left to right.
+    Quadrant,
+    /// Looks the same after every quarter turn, which needs a square board.
+    #[value(alias = "4-fold")]
+    FourFold,
+}
+
+impl SymmetryMode {
+    fn symmetry(self) -> Symmetry {
+        match self {
+            SymmetryMode::Horizontal => Symmetry::Horizontal,
+            SymmetryMode::Vertical => Symmetry::Vertical,
+            SymmetryMode::Quadrant => Symmetry::Quadrant,
+            SymmetryMode::FourFold => Symmetry::FourFold,
+        }
+    }
+}
+
+/// Which side of the terminal the board is drawn against.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Anchor {
+    Left,
+    Center,
+    Right,
+}
+
+/// Which way the population has to go for --stop-at-pop to stop the game.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum PopDirection {
+    /// Stop once the population is at least the target.
+    Above,
+    /// Stop once the population is below the target.
+    Below,
+}
+
+/// Where the status line is drawn relative to the board.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum StatusPosition {
+    Top,
+    Bottom,
+    Off,
 }
 
 /// Contains vectors of coordinate setups that make cool patterns.
@@ -171,233 +765,1547 @@ impl Pattern {
     }
 }
 
-fn parse_coordinate_pair(s: &str) -> Result<(usize, usize), String> {
-    match s.split(',').collect::<Vec<&str>>()[..] {
-        [x, y] => match (x.parse::<usize>(), y.parse::<usize>()) {
-            (Ok(x), Ok(y)) => Ok((x, y)),
-            _ => Err("Unable to parse coordinate pair.".to_owned()),
-        },
-        _ => Err("Encountered invalid coordinate set when parsing coordinates".to_owned()),
+/// Makes the board the cli asked for, from a file, image, pattern or cells, or otherwise randomly.
+/// Anything printed here is shown before the game starts.
+fn build_board(cli: &Cli, builder: ConwayBuilder) -> Result<Conway, String> {
+    #[cfg(feature = "image")]
+    let image = match &cli.image {
+        Some(path) => {
+            let image = load_image(path, cli.width.zip(cli.height))?;
+            if !cli.quiet {
+                println!(
+                    "Loaded the image from {}, using it. (total: {}, size: {}x{})",
+                    path.display(),
+                    image.cells.len(),
+                    image.width,
+                    image.height
+                );
+            }
+            Some(image)
+        }
+        None => None,
+    };
+    #[cfg(not(feature = "image"))]
+    let image: Option<PatternFile> = None;
+
+    let mut conway;
+    if let Some(path) = &cli.replay {
+        conway = load_replay(path, builder, cli.quiet)?;
+    } else if let Some(path) = &cli.load_bin {
+        let bytes = fs::read(path)
+            .map_err(|e| format!("Unable to read the board from {}: {e}", path.display()))?;
+        conway = builder
+            .build_from_bytes(&bytes)
+            .map_err(|e| e.to_string())?;
+        if !cli.quiet {
+            println!(
+                "Loaded the board from {}, using it. (total: {}, size: {}x{})",
+                path.display(),
+                conway.population(),
+                conway.width(),
+                conway.height()
+            );
+        }
+    } else if let Some(image) = image {
+        conway = builder.size(image.width, image.height).build();
+        conway
+            .check_fits(&image.cells, (0, 0))
+            .map_err(|e| e.to_string())?;
+        conway
+            .revive_cells(&image.cells)
+            .map_err(|e| e.to_string())?;
+    } else if let Some(path) = &cli.file {
+        let file = load_pattern_file(path, cli.width.is_some() && cli.height.is_some())?;
+        if !cli.quiet {
+            println!(
+                "Loaded the pattern from {}, using it. (total: {}, rule: {})",
+                path.display(),
+                file.cells.len(),
+                file.rule
+            );
+        }
+        conway = builder.rule(file.rule).build();
+        place_pattern(&mut conway, &file.cells, cli.at, cli.quiet)?;
+    } else if let Some(pattern) = cli.pattern {
+        if !cli.quiet {
+            println!("Found a pattern argument, using it. ({pattern})");
+        }
+        conway = match cli.at {
+            Origin::TopLeft => {
+                let (x, y) = pattern.size();
+                builder.size(x, y).build()
+            }
+            Origin::Center => builder.build(),
+        };
+        place_pattern(&mut conway, &pattern.coordinates(), cli.at, cli.quiet)?;
+    } else {
+        conway = builder.build();
+
+        if let Some(cells) = &cli.cells {
+            place_cells(&mut conway, cli, cells)?;
+        } else {
+            generate(&mut conway, cli)?;
+        }
+    }
+
+    if conway.is_hex() && conway.wraps() && !conway.height().is_multiple_of(2) {
+        return Err(format!(
+            "A wrapped hex grid needs an even height for its top and bottom rows to line up, but the board is {}x{}.",
+            conway.width(),
+            conway.height()
+        ));
+    }
+    warn_explosive_rule(conway.rule());
+    Ok(conway)
+}
+
+/// Brings the cells given with --cells to life, warning about any that were given more than once.
+fn place_cells(conway: &mut Conway, cli: &Cli, cells: &[(isize, isize)]) -> Result<(), String> {
+    if !cli.quiet {
+        println!(
+            "Found cells as an argument, using them instead of RNG. (total: {})",
+            cells.len()
+        );
+    }
+    let mut placed = HashSet::with_capacity(cells.len());
+    let mut duplicates = Vec::new();
+    let mut coords = Vec::with_capacity(cells.len());
+    for &coordinates in cells {
+        if !placed.insert(coordinates) {
+            if !duplicates.contains(&coordinates) {
+                duplicates.push(coordinates);
+            }
+            continue;
+        }
+        coords.push(board_coordinates(coordinates, cli.origin, conway)?);
+    }
+    report_skipped(
+        conway.revive_cells(&coords).map_err(|e| e.to_string())?,
+        cli.quiet,
+    );
+    if !duplicates.is_empty() {
+        eprintln!(
+            "Warning: Some cells were given more than once and were only placed once: {}",
+            duplicates
+                .iter()
+                .map(|(x, y)| format!("{x},{y}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+    }
+    Ok(())
+}
+
+/// Warns that rules where cells come alive with fewer than two live neighbors fill the board almost straight away,
+/// which is rarely what's wanted.
+fn warn_explosive_rule(rule: Rule) {
+    if rule.births_with(0) {
+        eprintln!(
+            "Warning: With the rule {rule}, dead cells with no live neighbors come alive, so empty space keeps coming alive, including past the edge of a board that doesn't wrap."
+        );
+    } else if rule.births_with(1) {
+        eprintln!(
+            "Warning: With the rule {rule}, dead cells with one live neighbor come alive, so any pattern will quickly spread across the board."
+        );
     }
 }
 
+/// Prints the settings that the game would run with, for --dry-run.
+fn print_settings(conway: &Conway, cli: &Cli, seed: Option<u64>) {
+    println!("Size: {}x{}", conway.width(), conway.height());
+    println!("Rule: {}", conway.rule());
+    println!("Wrap: {}", conway.wraps());
+    println!("Hex: {}", conway.is_hex());
+    match cli.gps {
+        Some(gps) => println!("Speed: {gps} generations per second"),
+        None => println!("Speed: {}ms per generation", cli.delay),
+    }
+    match (seed, &cli.replay) {
+        (Some(seed), _) => println!("Seed: {seed}"),
+        (None, Some(_)) => println!("Seed: from the replay"),
+        (None, None) => println!("Seed: random"),
+    }
+    println!("Start: {}", start_description(cli));
+    if let Some(dead) = &cli.dead {
+        println!("Kept dead: {} cells", dead.len());
+    }
+    println!("Population: {}", conway.population());
+    match cli.max_gen {
+        0 => println!("Max generations: none"),
+        max_gen => println!("Max generations: {max_gen}"),
+    }
+    match population_target(cli) {
+        Some(PopulationTarget::AtLeast(n)) => println!("Stop at population: at least {n}"),
+        Some(PopulationTarget::Below(n)) => println!("Stop at population: below {n}"),
+        None => (),
+    }
+    println!("Theme: {}", value_name(&cli.theme));
+    println!("Heatmap: {}", cli.heatmap);
+    println!("Dead cells: {:?}", cli.dead_char);
+    if let Some(color) = cli.dead_color {
+        println!("Dead color: {color:?}");
+    }
+    println!("Anchor: {}", value_name(&cli.anchor));
+    println!("Status: {}", value_name(&cli.status));
+}
+
+/// Describes where the starting board comes from.
+fn start_description(cli: &Cli) -> String {
+    #[cfg(feature = "image")]
+    if let Some(path) = &cli.image {
+        return format!("the image {}", path.display());
+    }
+    if let Some(path) = &cli.replay {
+        format!("the replay {}", path.display())
+    } else if let Some(path) = &cli.load_bin {
+        format!("the saved board {}", path.display())
+    } else if let Some(path) = &cli.file {
+        format!(
+            "the pattern file {}, placed at the {}",
+            path.display(),
+            value_name(&cli.at)
+        )
+    } else if let Some(pattern) = cli.pattern {
+        format!(
+            "the {pattern} pattern, placed at the {}",
+            value_name(&cli.at)
+        )
+    } else if let Some(cells) = &cli.cells {
+        format!(
+            "{} given cells, measured from the {}",
+            cells.len(),
+            value_name(&cli.origin)
+        )
+    } else if cli.clustered {
+        "random clusters of cells".to_owned()
+    } else if let Some(symmetry) = cli.symmetric {
+        format!("random cells with {} symmetry", value_name(&symmetry))
+    } else if let Some(n) = cli.num_cells {
+        format!("{n} random cells")
+    } else {
+        "random cells, each with a 50% chance of being alive".to_owned()
+    }
+}
+
+/// Returns the name an option's value is given by on the command line.
+fn value_name(value: &impl ValueEnum) -> String {
+    value
+        .to_possible_value()
+        .map_or_else(String::new, |value| value.get_name().to_owned())
+}
+
+/// Parses a rate of generations per second, which has to be more than 0.
+fn parse_gps(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(gps) if gps > 0.0 && gps.is_finite() => Ok(gps),
+        Ok(_) => Err("The rate has to be more than 0 generations per second.".to_owned()),
+        Err(_) => Err("Unable to parse the rate.".to_owned()),
+    }
+}
+
+/// Parses the character for dead cells, which has to be a single column wide like the blocks alive cells are drawn with.
+/// Anything wider, like most emoji, would push the rest of its row out of line.
+fn parse_dead_char(s: &str) -> Result<char, String> {
+    let mut chars = s.chars();
+    let (Some(c), None) = (chars.next(), chars.next()) else {
+        return Err("Expected a single character.".to_owned());
+    };
+    match c.width() {
+        Some(1) => Ok(c),
+        _ => Err(format!(
+            "{c:?} isn't a single column wide, so it would break the board's alignment."
+        )),
+    }
+}
+
+/// Parses a width or height for the board, which has to be at least 1.
+fn parse_dimension(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(0) => Err("The board has to be at least 1 cell wide and tall.".to_owned()),
+        Ok(n) => Ok(n),
+        Err(_) => Err("Unable to parse board dimension.".to_owned()),
+    }
+}
+
+/// Parses one of the terminal's named colors, such as red or `dark_blue`.
+fn parse_color(s: &str) -> Result<Color, String> {
+    Color::try_from(s.replace('-', "_").as_str()).map_err(|()| {
+        "Unknown color, expected one of black, red, green, yellow, blue, magenta, cyan, white, grey \
+         or a dark version of them like dark_red."
+            .to_owned()
+    })
+}
+
+/// Brings a pattern to life where --at asks for it. In the center, it's the pattern's live cells that are centered,
+/// so any empty space around them doesn't push them off to one side.
+fn place_pattern(
+    conway: &mut Conway,
+    cells: &[(usize, usize)],
+    at: Origin,
+    quiet: bool,
+) -> Result<(), String> {
+    let Origin::Center = at else {
+        report_skipped(
+            conway.place(cells, (0, 0)).map_err(|e| e.to_string())?,
+            quiet,
+        );
+        return Ok(());
+    };
+    let min_x = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+    let min_y = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+    let cells: Vec<(usize, usize)> = cells.iter().map(|&(x, y)| (x - min_x, y - min_y)).collect();
+    let width = cells.iter().map(|&(x, _)| x + 1).max().unwrap_or(0);
+    let height = cells.iter().map(|&(_, y)| y + 1).max().unwrap_or(0);
+    let offset = (
+        conway.width().saturating_sub(width) / 2,
+        conway.height().saturating_sub(height) / 2,
+    );
+    report_skipped(
+        conway.place(&cells, offset).map_err(|e| e.to_string())?,
+        quiet,
+    );
+    Ok(())
+}
+
+/// Tells the user how many cells were skipped for already being alive, unless --quiet is given.
+fn report_skipped(skipped: usize, quiet: bool) {
+    if skipped > 0 && !quiet {
+        println!("{skipped} of the cells were already alive, skipping them...");
+    }
+}
+
+/// Turns coordinates given relative to the origin into coordinates on the board.
+fn board_coordinates(
+    (x, y): (isize, isize),
+    origin: Origin,
+    conway: &Conway,
+) -> Result<(usize, usize), String> {
+    let (origin_x, origin_y) = match origin {
+        Origin::TopLeft => (-1, -1),
+        Origin::Center => (
+            (conway.width() / 2) as isize,
+            (conway.height() / 2) as isize,
+        ),
+    };
+    let board_x = origin_x
+        .checked_add(x)
+        .and_then(|x| usize::try_from(x).ok());
+    let board_y = origin_y
+        .checked_add(y)
+        .and_then(|y| usize::try_from(y).ok());
+    match (board_x, board_y) {
+        (Some(x), Some(y)) => Ok((x, y)),
+        _ => Err(format!(
+            "Coordinate pair {x},{y} was out of bounds for board size {}x{}",
+            conway.width(),
+            conway.height()
+        )),
+    }
+}
+
+/// Builds the board saved in a replay, with the rest of its options coming from ``builder``.
+fn load_replay(path: &Path, builder: ConwayBuilder, quiet: bool) -> Result<Conway, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Unable to read replay {}: {e}", path.display()))?;
+    let replay = parse_replay(&contents).map_err(|e| e.to_string())?;
+    let pattern = replay.pattern;
+    if !quiet {
+        println!(
+            "Loaded the replay from {}, using it. (size: {}x{}, rule: {})",
+            path.display(),
+            pattern.width,
+            pattern.height,
+            pattern.rule
+        );
+    }
+    let mut builder = builder
+        .size(pattern.width, pattern.height)
+        .wrap(replay.wrap)
+        .hex(replay.hex)
+        .rule(pattern.rule);
+    if let Some(seed) = replay.seed {
+        builder = builder.seed(seed);
+    }
+    let mut conway = builder.build();
+    conway
+        .revive_cells(&pattern.cells)
+        .map_err(|e| e.to_string())?;
+    Ok(conway)
+}
+
+/// The extensions of the pattern files that --pattern-lib picks from.
+const LIBRARY_EXTENSIONS: [&str; 2] = ["rle", "cells"];
+
+/// Returns the names and paths of the patterns in a --pattern-lib directory, sorted by name.
+fn library_patterns(dir: &Path) -> Result<Vec<(String, PathBuf)>, String> {
+    let read_error = |e| format!("Unable to read pattern library {}: {e}", dir.display());
+    let mut patterns = Vec::new();
+    for entry in fs::read_dir(dir).map_err(read_error)? {
+        let path = entry.map_err(read_error)?.path();
+        let is_pattern = path
+            .extension()
+            .and_then(OsStr::to_str)
+            .is_some_and(|extension| LIBRARY_EXTENSIONS.contains(&extension));
+        let name = path.file_stem().and_then(OsStr::to_str).map(str::to_owned);
+        if let Some(name) = name.filter(|_| is_pattern && path.is_file()) {
+            patterns.push((name, path));
+        }
+    }
+    patterns.sort();
+    Ok(patterns)
+}
+
+/// Finds the file of the pattern called ``name`` in a --pattern-lib directory.
+fn find_library_pattern(dir: &Path, name: &str) -> Result<PathBuf, String> {
+    let patterns = library_patterns(dir)?;
+    let mut found = patterns.iter().filter(|(pattern, _)| pattern == name);
+    match (found.next(), found.next()) {
+        (Some((_, path)), None) => Ok(path.clone()),
+        (Some(_), Some(_)) => Err(format!(
+            "There's more than one pattern named {name} in {}, so pick one with --file instead.",
+            dir.display()
+        )),
+        (None, _) => Err(format!(
+            "Unable to find a pattern named {name} in {}. Try --list-patterns to see what's there.",
+            dir.display()
+        )),
+    }
+}
+
+/// Prints the built-in patterns, and the patterns in --pattern-lib if one was given, for --list-patterns.
+fn list_patterns(cli: &Cli) -> Result<(), String> {
+    println!("Built-in patterns, for --pattern:");
+    for pattern in Pattern::value_variants() {
+        if let Some(value) = pattern.to_possible_value() {
+            println!("  {}", value.get_name());
+        }
+    }
+    if let Some(dir) = &cli.pattern_lib {
+        println!("Patterns in {}, for --pattern-name:", dir.display());
+        for (name, path) in library_patterns(dir)? {
+            println!("  {name} ({})", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Reads a pattern file, picking the format from its extension.
+/// Life 1.06 files have no size of their own, so they can only be loaded onto a board with a given size.
+fn load_pattern_file(path: &Path, sized: bool) -> Result<PatternFile, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Unable to read pattern file {}: {e}", path.display()))?;
+    match path.extension().and_then(OsStr::to_str) {
+        Some("rle") => parse_rle(&contents).map_err(|e| e.to_string()),
+        Some("cells") => parse_plaintext(&contents).map_err(|e| e.to_string()),
+        Some("lif" | "life") if !sized => Err(format!(
+            "{} is a Life 1.06 file which has no size, so the width and height of the board need to be given.",
+            path.display()
+        )),
+        Some("lif" | "life") => parse_life106(&contents).map_err(|e| e.to_string()),
+        _ => Err(format!(
+            "Unable to tell the format of {}, expected a .rle, .cells, .lif or .life file.",
+            path.display()
+        )),
+    }
+}
+
+/// Reads an image, scaling it to the size if there is one, and turns its dark pixels into live cells.
+/// Transparent pixels are dead no matter their color.
+#[cfg(feature = "image")]
+fn load_image(path: &Path, size: Option<(usize, usize)>) -> Result<PatternFile, String> {
+    let mut image = image::open(path)
+        .map_err(|e| format!("Unable to read image {}: {e}", path.display()))?
+        .into_luma_alpha8();
+    if let Some((width, height)) = size {
+        image = image::imageops::resize(
+            &image,
+            width as u32,
+            height as u32,
+            image::imageops::FilterType::Triangle,
+        );
+    }
+    let cells: Vec<(usize, usize)> = image
+        .enumerate_pixels()
+        .filter(|(_, _, pixel)| {
+            let [luma, alpha] = pixel.0;
+            luma < 128 && alpha >= 128
+        })
+        .map(|(x, y, _)| (x as usize, y as usize))
+        .collect();
+    Ok(PatternFile {
+        width: image.width() as usize,
+        height: image.height() as usize,
+        cells,
+        rule: Rule::LIFE,
+    })
+}
+
 fn clear_screen() -> Result<(), String> {
     execute!(io::stdout(), terminal::Clear(terminal::ClearType::All))
         .map_err(|_| "Unable to clear screen.")?;
     Ok(())
 }
 
-/// Represents the current state of a cell, either alive or dead.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum CellState {
-    Alive,
-    Dead,
+/// Fills the board with random cells, the way the cli asked for.
+fn generate(conway: &mut Conway, cli: &Cli) -> Result<(), String> {
+    if cli.clustered {
+        // A blob for roughly every 20x20 area, each spanning about half of the board's shorter side.
+        let clusters = (conway.width() * conway.height() / 400).max(1);
+        let radius = (conway.width().min(conway.height()) / 4).max(2);
+        conway.generate_clustered_board(clusters, radius);
+    } else if let Some(symmetry) = cli.symmetric {
+        conway
+            .generate_symmetric_board(symmetry.symmetry())
+            .map_err(|e| e.to_string())?;
+    } else if let Some(n) = cli.num_cells {
+        conway.generate_board(n).map_err(|e| e.to_string())?;
+    } else {
+        conway.generate_random_board();
+    }
+    if let Some(dead) = &cli.dead {
+        let coords = dead
+            .iter()
+            .map(|&coordinates| board_coordinates(coordinates, cli.origin, conway))
+            .collect::<Result<Vec<_>, _>>()?;
+        conway.kill_cells(&coords).map_err(|e| e.to_string())?;
+    }
+    Ok(())
 }
 
-/// Representation of a Conway's game of life board.
-struct Conway {
-    cells: Vec<CellState>,
-    rng: StdRng,
-    width: usize,
-    height: usize,
+/// Runs the board for --count-only until it stops changing, starts repeating or hits the generation cap,
+/// and prints a summary of the run.
+fn count_only(conway: &mut Conway, cli: &Cli, skipped: u64) -> Result<(), String> {
+    let mut cycles = CycleDetector::new(cycle_history(conway));
+    cycles.record(conway, skipped);
+    // Generations carry on from any that were skipped, which the cap counts too.
+    let max_gen = (cli.max_gen > 0).then_some(cli.max_gen);
+    let mut run = RunSummary {
+        generations: skipped,
+        ..RunSummary::default()
+    };
+    let mut period = None;
+    let target = population_target(cli);
+    // Spaceships only end the count once the whole board repeats, like they do when running it.
+    while period.is_none()
+        && conway
+            .advance(&mut run, max_gen, target)
+            .map_err(|e| e.to_string())?
+    {
+        period = cycles
+            .record(conway, run.generations)
+            .filter(|cycle| !cycle.moves());
+    }
+
+    let end = match run.end {
+        _ if period.is_some() => "cycle",
+        Some(RunEnd::Stable) if run.population == 0 => "extinct",
+        Some(RunEnd::Stable) => "stable",
+        Some(RunEnd::Capped) => "capped",
+        Some(RunEnd::TargetReached) => "target",
+        None => "stopped",
+    };
+    println!("end={end}");
+    println!("generations={}", run.generations);
+    println!("final_population={}", run.population);
+    println!("peak_population={}", run.peak_population);
+    println!("peak_generation={}", run.peak_generation);
+    println!(
+        "period={}",
+        period.map_or_else(|| "none".to_owned(), |cycle| cycle.period.to_string())
+    );
+    if cli.print_final {
+        print!("{}", conway.render_string());
+    }
+    Ok(())
 }
 
-/// Represents coordinates of neighbors in the form of offset of x, y
-const NEIGHBOR_COORDINATES: [(i32, i32); 8] = [
-    (-1, -1), // Top Left
-    (0, -1),  // Above
-    (1, -1),  // Top Right
-    (-1, 0),  // Left
-    (1, 0),   // Right
-    (-1, 1),  // Bottom Left
-    (0, 1),   // Below
-    (1, 1),   // Bottom Right
-];
+/// Runs the board until it stops changing, redrawing it every tick.
+/// Generations are counted from ``start``, for boards that have already been ticked before the game started.
+fn game_loop(conway: &mut Conway, cli: &Cli, mut skipped: u64) -> Result<(), String> {
+    let mut clipboard = Clipboard::new();
+    let mut generation = skipped;
+    let mut period = None;
+    let mut exited;
+    // The time spent ticking and drawing is taken out of the wait, so that generations stay evenly spaced.
+    let interval = frame_interval(cli);
+    let mut rate = Rate::default();
+    let mut history = History::new(cli.palindrome);
+    // The cap counts from the first generation, including any that were skipped.
+    let max_gen = (cli.max_gen > 0).then_some(cli.max_gen);
+    let target = population_target(cli);
+    let mut run;
+    if cli.start_paused && !start_paused(conway, cli, &mut clipboard, generation)? {
+        return Ok(());
+    }
+    loop {
+        // How many generations in a row every change has been near the edge of the board.
+        let mut edge_streak = 0;
+        let mut cycles = CycleDetector::new(cycle_history(conway));
+        cycles.record(conway, generation);
+        log_generation(generation, conway);
+        history.record(conway, generation);
+        run = RunSummary {
+            generations: generation,
+            ..RunSummary::default()
+        };
+        loop {
+            let frame = Instant::now();
+            if !conway
+                .advance(&mut run, max_gen, target)
+                .map_err(|e| e.to_string())?
+            {
+                break;
+            }
+            generation = run.generations;
+            rate.record();
+            history.record(conway, generation);
+            if conway.edge_changes() == conway.births() + conway.deaths() {
+                edge_streak += 1;
+            } else {
+                edge_streak = 0;
+            }
+            log_generation(generation, conway);
+            let repeated = cycles.record(conway, generation);
+            if period.is_none() {
+                log_cycle(repeated, generation);
+            }
+            period = repeated;
+            clear_screen()?;
+            print(conway, cli, generation, period, rate.rate)?;
+            let delay = ramped_interval(cli, interval, generation.saturating_sub(skipped + 1))
+                .saturating_sub(frame.elapsed());
+            if !between_generations(conway, cli, &mut clipboard, generation, delay, &mut rate)? {
+                return Ok(());
+            }
+
+            if cli.screensaver && conway.population() < cli.min_population {
+                log_population_dropped(cli.min_population, generation);
+                break;
+            }
+            if stops_on_cycle(cli, period) {
+                break;
+            }
+        }
 
-const RESET: &str = "\x1B[0m";
+        exited = log_run_end(conway, &run, edge_streak);
+        // Hitting a limit the user set ends the game, even when it would otherwise start over.
+        let limited = matches!(run.end, Some(RunEnd::Capped | RunEnd::TargetReached));
+        if limited || !(cli.loop_on_stable || cli.screensaver) {
+            break;
+        }
+        // Show how the board ended up before starting over. This also keeps boards that stop straight away,
+        // like a 1x1 board, from starting over so fast that there's no chance to quit.
+        clear_screen()?;
+        print(conway, cli, generation, period, rate.rate)?;
+        if !wait(conway, &mut clipboard, interval)? {
+            return Ok(());
+        }
+        rate.restart();
+        conway.clear();
+        generate(conway, cli)?;
+        generation = 0;
+        // Nothing is skipped on the new board, so --ramp starts over from its first generation.
+        skipped = 0;
+        period = None;
+    }
 
-impl Conway {
-    /// Returns a Conway's board with the size of x, y
-    fn new(width: usize, height: usize, rng: StdRng) -> Self {
-        Self {
-            cells: vec![CellState::Dead; width * height],
-            rng,
-            width,
-            height,
-        }
-    }
-
-    fn revive_cell(&mut self, x: usize, y: usize) -> Result<(), String> {
-        let Some(cell) = self.cells.get(x + y * self.width) else {
-            return Err(format!(
-                "The coordinate pair {},{} was out of bounds for size {}x{}.",
-                x + 1,
-                y + 1,
-                self.width,
-                self.height
-            ));
-        };
-        if matches!(cell, CellState::Alive) {
-            println!(
-                "The cell with coordinates {}, {} was already alive, skipping...",
-                x + 1,
-                y + 1
+    if cli.palindrome && run.end != Some(RunEnd::Capped) && conway.population() == 0 {
+        return history.play(cli, &mut clipboard);
+    }
+    // print the last board before it stopped ticking.
+    print(conway, cli, generation, period, rate.rate)?;
+    let reason = end_reason(cli, exited, &run, period);
+    end(conway, cli, &mut clipboard, reason.as_deref(), &run)
+}
+
+/// Shows the starting board for --start-paused, holding it until a key is pressed.
+/// This will return ``false`` if the user asked to quit, else it will return ``true``.
+fn start_paused(
+    conway: &Conway,
+    cli: &Cli,
+    clipboard: &mut Clipboard,
+    generation: u64,
+) -> Result<bool, String> {
+    clear_screen()?;
+    print(conway, cli, generation, None, None)?;
+    pause(conway, clipboard, "Paused, press any button to start.")
+}
+
+/// Waits out the delay before the next generation, then does anything that was asked for while waiting
+/// and pauses if it's time to. This will return ``false`` if the user asked to quit, else it will return ``true``.
+fn between_generations(
+    conway: &mut Conway,
+    cli: &Cli,
+    clipboard: &mut Clipboard,
+    generation: u64,
+    delay: Duration,
+    rate: &mut Rate,
+) -> Result<bool, String> {
+    if !wait(conway, clipboard, delay)? {
+        return Ok(false);
+    }
+    if RECENTER.swap(false, Ordering::Relaxed) {
+        conway.recenter();
+    }
+    auto_pause(conway, cli, clipboard, generation, rate)
+}
+
+/// Pauses if --auto-pause asks for it at this generation, leaving the time spent paused out of the rate.
+/// This will return ``false`` if the user asked to quit, else it will return ``true``.
+fn auto_pause(
+    conway: &Conway,
+    cli: &Cli,
+    clipboard: &mut Clipboard,
+    generation: u64,
+    rate: &mut Rate,
+) -> Result<bool, String> {
+    let Some(n) = cli.auto_pause.filter(|&n| generation.is_multiple_of(n)) else {
+        return Ok(true);
+    };
+    let prompt = format!("Paused, press any button to run another {n} generations.");
+    let unpaused = pause(conway, clipboard, &prompt)?;
+    rate.restart();
+    Ok(unpaused)
+}
+
+/// Logs why a run ended, returning whether a pattern appears to have walked off the edge of the board.
+fn log_run_end(conway: &Conway, run: &RunSummary, edge_streak: u32) -> bool {
+    match run.end {
+        Some(RunEnd::Stable) => {
+            // A pattern that only changed near the edge right before dying out most likely walked off of it.
+            let exited = conway.population() == 0 && !conway.wraps() && edge_streak >= EXIT_STREAK;
+            log_stopped(conway, run.generations, exited);
+            exited
+        }
+        Some(RunEnd::Capped) => {
+            #[cfg(feature = "logging")]
+            log::info!("Stopped at the generation cap of {}.", run.generations);
+            false
+        }
+        Some(RunEnd::TargetReached) => {
+            #[cfg(feature = "logging")]
+            log::info!(
+                "Stopped at a population of {} after {} generations.",
+                run.population,
+                run.generations
             );
-            Ok(())
-        } else {
-            self.set_cell(x, y, CellState::Alive)
+            false
+        }
+        None => false,
+    }
+}
+
+/// Returns why the game ended, if it's for a reason worth telling the user about.
+fn end_reason(cli: &Cli, exited: bool, run: &RunSummary, period: Option<Cycle>) -> Option<String> {
+    match run.end {
+        _ if exited => Some("A pattern appears to have exited the board.".to_owned()),
+        None if cli.stop_on_oscillators && period.is_some_and(Cycle::is_short) => {
+            Some("Board reduced to static oscillators.".to_owned())
         }
+        Some(RunEnd::Capped) => Some(format!(
+            "Stopped at the generation cap of {}, use --max-gen 0 to keep going.",
+            cli.max_gen
+        )),
+        Some(RunEnd::TargetReached) => Some(format!(
+            "Stopped at generation {} with a population of {}.",
+            run.generations, run.population
+        )),
+        _ => None,
     }
+}
 
-    fn game_loop(&mut self) -> Result<(), String> {
-        while self.tick()? {
-            clear_screen()?;
-            self.print()?;
-            println!();
-            thread::sleep(Duration::from_millis(500));
-        }
-        // print the last board before it stopped ticking.
-        self.print()?;
-        println!("Press any button to exit.");
-        let mut buffer = String::new();
-        io::stdin()
-            .read_line(&mut buffer)
-            .map_err(|_| "Unable to read stdin.")?;
+/// Returns whether the game stops now that the board is repeating the way ``period`` says, if it is.
+/// Spaceships never stop it, as they'll run into the edge or come back around to where they started.
+fn stops_on_cycle(cli: &Cli, period: Option<Cycle>) -> bool {
+    period.filter(|cycle| !cycle.moves()).is_some_and(|cycle| {
+        cli.screensaver || cli.stop_on_cycle || (cli.stop_on_oscillators && cycle.is_short())
+    })
+}
 
-        Ok(())
+/// Returns the population that --stop-at-pop stops the game at, if there is one.
+fn population_target(cli: &Cli) -> Option<PopulationTarget> {
+    cli.stop_at_pop.map(|n| match cli.pop_direction {
+        PopDirection::Above => PopulationTarget::AtLeast(n),
+        PopDirection::Below => PopulationTarget::Below(n),
+    })
+}
+
+/// Returns the time between the start of one generation and the next.
+fn frame_interval(cli: &Cli) -> Duration {
+    match cli.gps {
+        Some(gps) => Duration::from_secs_f64(1.0 / gps),
+        None => Duration::from_millis(cli.delay),
     }
+}
 
-    fn print(&self) -> Result<(), String> {
-        static OFFSET: OnceLock<usize> = OnceLock::new();
-        let (w, _) = *SIZE.get().expect("Somehow the terminal size wasn't set.");
-        let offset = OFFSET.get_or_init(|| {
-            if self.width >= w {
-                0
-            } else {
-                (w / 2) - (self.width / 2)
-            }
-        });
-        for row in self.cells.chunks(self.width) {
-            print!("{}", " ".repeat(*offset));
-            for cell in row {
-                match cell {
-                    CellState::Alive => {
-                        execute!(io::stdout(), style::PrintStyledContent("█".green()))
-                            .map_err(|_| "Unable to write to stdout.")?;
-                    }
-                    CellState::Dead => print!(" "),
-                }
+/// Returns how long to show a generation for once ``shown`` generations have been shown during --ramp.
+/// This goes evenly from where the ramp starts down to ``interval``, and then stays there.
+fn ramped_interval(cli: &Cli, interval: Duration, shown: u64) -> Duration {
+    let Some(ramp) = cli.ramp.filter(|&ramp| shown < ramp) else {
+        return interval;
+    };
+    // Starting any faster than the ramp ends would make it a slow-down instead.
+    let slower = Duration::from_millis(cli.ramp_from).saturating_sub(interval);
+    let left = (ramp - shown) as f64 / ramp as f64;
+    interval + slower.mul_f64(left)
+}
+
+/// Tells the user the game is over, along with why if there's something worth saying, and waits for them to leave.
+fn end(
+    conway: &Conway,
+    cli: &Cli,
+    clipboard: &mut Clipboard,
+    reason: Option<&str>,
+    run: &RunSummary,
+) -> Result<(), String> {
+    if !cli.quiet {
+        if let Some(reason) = reason {
+            print!("{reason}\r\n");
+        }
+        print!(
+            "Peak population: {} at generation {}.\r\n",
+            run.peak_population, run.peak_generation
+        );
+        if cli.interactive_end {
+            print!("Stopped, the keys still work until q is pressed to exit.\r\n");
+        } else {
+            print!("Press any button to exit.\r\n");
+        }
+    }
+    io::stdout()
+        .flush()
+        .map_err(|_| "Unable to write to stdout.")?;
+    if cli.interactive_end {
+        while wait(conway, clipboard, Duration::from_mins(1))? {}
+    } else if !cli.quiet {
+        read_key()?;
+    }
+    Ok(())
+}
+
+/// Waits for a single key to be pressed, which needs raw mode to not wait for enter.
+/// This will return ``false`` if it was one of the keys that quit, else it will return ``true``.
+fn read_key() -> Result<bool, String> {
+    loop {
+        if let Event::Key(key) = event::read().map_err(|_| "Unable to read input.")? {
+            if key.kind == KeyEventKind::Press {
+                return Ok(!is_quit(key));
             }
-            println!("{RESET}");
         }
-        Ok(())
     }
+}
 
-    /// Randomly generates a board with a given amount of cells.
-    fn generate_board(&mut self, cells: usize) -> Result<(), String> {
-        for _ in 0..cells {
-            loop {
-                let x = self.rng.gen_range(0..self.width);
-                let y = self.rng.gen_range(0..self.height);
-                if let Some(cell) = self.get_cell(x, y) {
-                    // if the cell is not already alive, then make it so
-                    match cell {
-                        CellState::Alive => (),
-                        CellState::Dead => {
-                            self.set_cell(x, y, CellState::Alive)?;
-                            break;
-                        }
-                    }
+/// How many generations in a row the changes have to stay near the edge for a board that dies out
+/// to count as a pattern leaving it, rather than dying out on its own.
+const EXIT_STREAK: u32 = 4;
+
+/// How many of the most recent boards are remembered when looking for a repeat.
+/// This is also the longest period that can be noticed.
+const CYCLE_HISTORY: usize = 256;
+
+/// The most boards that are remembered on a wrapped board, however long its spaceships take to come back around.
+const MAX_CYCLE_HISTORY: usize = 1 << 16;
+
+/// Returns how many boards to remember when looking for a repeat.
+/// Spaceships on a wrapped board come back around to where they started, making the whole board repeat.
+/// The slowest common one is the glider, which moves a cell diagonally every 4 generations,
+/// so it needs to cover a multiple of both the width and height before it's back.
+fn cycle_history(conway: &Conway) -> usize {
+    if !conway.wraps() {
+        return CYCLE_HISTORY;
+    }
+    let lcm = conway.width() / gcd(conway.width(), conway.height()) * conway.height();
+    lcm.saturating_mul(4)
+        .clamp(CYCLE_HISTORY, MAX_CYCLE_HISTORY)
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The most boards that --palindrome keeps, after which the oldest are forgotten and the loop starts later on.
+const MAX_HISTORY: usize = 1000;
+
+/// The boards of a run, kept to be played back with --palindrome.
+/// Only which cells are alive is kept for each board, so decaying cells are played back as dead.
+struct History {
+    enabled: bool,
+    boards: VecDeque<(u64, Vec<u8>, TickCounts)>,
+    wrap: bool,
+    hex: bool,
+}
+
+impl History {
+    fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            boards: VecDeque::new(),
+            wrap: false,
+            hex: false,
+        }
+    }
+
+    /// Keeps the board as it is at the given generation, if history is being kept.
+    fn record(&mut self, conway: &Conway, generation: u64) {
+        if !self.enabled {
+            return;
+        }
+        if self.boards.len() == MAX_HISTORY {
+            self.boards.pop_front();
+        }
+        self.boards
+            .push_back((generation, conway.to_bytes(), TickCounts::of(conway)));
+        // Drawing a board depends on how its neighbors are found, so it's built the same way when played back.
+        (self.wrap, self.hex) = (conway.wraps(), conway.is_hex());
+    }
+
+    /// Plays the kept boards backwards and then forwards until the user quits.
+    /// The boards at either end are only shown once each time round, so the loop doesn't stutter.
+    fn play(&self, cli: &Cli, clipboard: &mut Clipboard) -> Result<(), String> {
+        let interval = frame_interval(cli);
+        loop {
+            let backwards = self.boards.iter().rev().skip(1);
+            let forwards = self.boards.iter().skip(1);
+            for &(generation, ref bytes, counts) in backwards.chain(forwards) {
+                let conway = ConwayBuilder::new()
+                    .seed(0)
+                    .wrap(self.wrap)
+                    .hex(self.hex)
+                    .build_from_bytes(bytes)
+                    .map_err(|e| e.to_string())?;
+                clear_screen()?;
+                print_frame(&conway, counts, cli, generation, None, None)?;
+                if !wait(&conway, clipboard, interval)? {
+                    return Ok(());
                 }
             }
         }
+    }
+}
+
+/// How many cells the last tick of a board brought to life, killed and changed, for the status line.
+/// These are kept apart from the board, so that boards played back with --palindrome can show theirs.
+#[derive(Clone, Copy)]
+struct TickCounts {
+    births: usize,
+    deaths: usize,
+    changes: usize,
+}
+
+impl TickCounts {
+    fn of(conway: &Conway) -> Self {
+        Self {
+            births: conway.births(),
+            deaths: conway.deaths(),
+            changes: conway.changed_cells(),
+        }
+    }
+}
+
+/// Measures how many generations are actually being run per second.
+#[derive(Default)]
+struct Rate {
+    last: Option<Instant>,
+    rate: Option<f64>,
+}
+
+impl Rate {
+    /// Records that a generation just ran.
+    fn record(&mut self) {
+        let now = Instant::now();
+        if let Some(last) = self.last {
+            let rate = 1.0 / now.duration_since(last).as_secs_f64().max(f64::EPSILON);
+            // Smooth the rate out so that it doesn't jump around between generations.
+            self.rate = Some(self.rate.map_or(rate, |last| last * 0.8 + rate * 0.2));
+        }
+        self.last = Some(now);
+    }
+
+    /// Starts measuring again with the next generation, after time that shouldn't count against the rate, like a pause.
+    fn restart(&mut self) {
+        self.last = None;
+    }
+}
+
+/// How the board repeats itself, every ``period`` generations and moved over by ``shift`` cells.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Cycle {
+    period: u64,
+    shift: (isize, isize),
+}
+
+impl Cycle {
+    /// Returns whether the board moves as it repeats, like a spaceship.
+    fn moves(self) -> bool {
+        self.shift != (0, 0)
+    }
+
+    /// Returns whether the board is only still lifes and oscillators like blinkers, which all flip back every other
+    /// generation.
+    fn is_short(self) -> bool {
+        !self.moves() && self.period <= 2
+    }
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.period)?;
+        if self.moves() {
+            write!(f, ", moving {},{}", self.shift.0, self.shift.1)?;
+        }
         Ok(())
     }
+}
 
-    fn generate_random_board(&mut self) {
-        for i in 0..self.cells.len() {
-            if self.rng.gen_range(0..=1) == 0 {
-                self.cells[i] = CellState::Alive;
-            }
+/// Remembers when the most recent boards were seen, to notice the board repeating itself.
+/// The shapes of the live cells are remembered too, along with a snapshot of the board, to notice spaceships
+/// repeating themselves further along before the whole board does.
+struct CycleDetector {
+    seen: HashMap<u64, u64>,
+    order: VecDeque<u64>,
+    shapes: HashMap<u64, (u64, Vec<u8>)>,
+    shape_order: VecDeque<u64>,
+    capacity: usize,
+}
+
+impl CycleDetector {
+    /// Returns a detector that remembers up to ``capacity`` boards.
+    fn new(capacity: usize) -> Self {
+        Self {
+            seen: HashMap::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+            shapes: HashMap::with_capacity(capacity),
+            shape_order: VecDeque::with_capacity(capacity),
+            capacity,
         }
     }
 
-    /// Returns the amount of neighbors that a cell has that are currently alive.
-    fn neighbors(&self, x: usize, y: usize) -> Result<usize, String> {
-        if self.get_cell(x, y).is_none() {
-            Err(format!("Coordinate pair {x},{y} was invalid."))?;
+    /// Records the board as it is at the given generation.
+    /// Returns how the board is repeating if the same board, or the same live cells somewhere else, were seen
+    /// in the last few generations. The same board is always preferred, so a spaceship that has come back around
+    /// a wrapped board doesn't count as moving.
+    fn record(&mut self, conway: &Conway, generation: u64) -> Option<Cycle> {
+        let mut hasher = DefaultHasher::new();
+        conway
+            .rows()
+            .flatten()
+            .for_each(|cell| cell.hash(&mut hasher));
+        let hash = hasher.finish();
+        let shifted = self.record_shape(conway, generation);
+
+        let period = self
+            .seen
+            .insert(hash, generation)
+            .map(|seen| generation - seen);
+        if period.is_none() {
+            remember(&mut self.order, &mut self.seen, hash, self.capacity);
         }
-        let mut neighbors: usize = 0;
-        for (offset_x, offset_y) in &NEIGHBOR_COORDINATES {
-            // Calculate the offest, and if it is invalid (i.e) -1, then skip it
-            let neighbor_x = (x as i32) + offset_x;
-            let neighbor_y = (y as i32) + offset_y;
-            if neighbor_x < 0i32 || neighbor_y < 0i32 {
-                continue;
-            }
+        period
+            .map(|period| Cycle {
+                period,
+                shift: (0, 0),
+            })
+            .or(shifted)
+    }
 
-            if let Some(neighbor) = self.get_cell(neighbor_x as usize, neighbor_y as usize) {
-                neighbors += match neighbor {
-                    CellState::Alive => 1,
-                    CellState::Dead => 0,
-                }
+    /// Records the shape of the live cells, going from the top left of their bounding box.
+    /// Returns how they're repeating if the same shape was seen somewhere else recently.
+    /// Only the last few shapes are kept, as each one holds a snapshot of the whole board.
+    fn record_shape(&mut self, conway: &Conway, generation: u64) -> Option<Cycle> {
+        let (left, top, ..) = conway.bounding_box()?;
+        let mut hasher = DefaultHasher::new();
+        conway
+            .alive_cells()
+            .for_each(|(x, y)| (x - left, y - top).hash(&mut hasher));
+        let hash = hasher.finish();
+
+        let Some((seen, snapshot)) = self.shapes.insert(hash, (generation, conway.to_bytes()))
+        else {
+            let capacity = self.capacity.min(CYCLE_HISTORY);
+            remember(&mut self.shape_order, &mut self.shapes, hash, capacity);
+            return None;
+        };
+        // Two shapes can share a hash, so it's only the same shape if the cells match too.
+        let shift = Conway::from_bytes(&snapshot).ok()?.translation_to(conway)?;
+        Some(Cycle {
+            period: generation - seen,
+            shift,
+        })
+        .filter(|cycle| cycle.moves())
+    }
+}
+
+/// Adds a newly seen hash to ``order``, forgetting the oldest one once there are more than ``capacity``.
+fn remember<T>(order: &mut VecDeque<u64>, seen: &mut HashMap<u64, T>, hash: u64, capacity: usize) {
+    order.push_back(hash);
+    if order.len() > capacity {
+        if let Some(oldest) = order.pop_front() {
+            seen.remove(&oldest);
+        }
+    }
+}
+
+/// Waits out the delay between generations, handling any keys that are pressed in the meantime.
+/// This will return ``false`` if the user asked to quit, else it will return ``true``.
+fn wait(conway: &Conway, clipboard: &mut Clipboard, delay: Duration) -> Result<bool, String> {
+    let deadline = Instant::now() + delay;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if !event::poll(remaining).map_err(|_| "Unable to poll for input.")? {
+            return Ok(true);
+        }
+        let Event::Key(key) = event::read().map_err(|_| "Unable to read input.")? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            _ if is_quit(key) => return Ok(false),
+            KeyCode::Char('c') => clipboard.copy(&conway.to_rle()),
+            // The next frame is drawn with or without the status line.
+            KeyCode::Char('h') => {
+                SHOW_STATUS.fetch_xor(true, Ordering::Relaxed);
             }
+            // Only the running game can move its cells, so it's left to the game loop.
+            KeyCode::Char('r') => RECENTER.store(true, Ordering::Relaxed),
+            _ => (),
         }
+    }
+}
 
-        Ok(neighbors)
+/// Holds the board until a key is pressed, telling the user what pressing one does with ``prompt``.
+/// This will return ``false`` if the user asked to quit, else it will return ``true``.
+fn pause(conway: &Conway, clipboard: &mut Clipboard, prompt: &str) -> Result<bool, String> {
+    print!("{prompt}\r\n");
+    io::stdout()
+        .flush()
+        .map_err(|_| "Unable to write to stdout.")?;
+    loop {
+        let Event::Key(key) = event::read().map_err(|_| "Unable to read input.")? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            _ if is_quit(key) => return Ok(false),
+            // Copying shouldn't also unpause, so the board can be copied before moving on.
+            KeyCode::Char('c') => clipboard.copy(&conway.to_rle()),
+            _ => return Ok(true),
+        }
     }
+}
 
-    fn get_cell(&self, x: usize, y: usize) -> Option<CellState> {
-        self.cells.get(x + y * self.width).copied()
+/// Returns whether the key is one of the keys that quit, which are q, escape and ctrl-c.
+fn is_quit(key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Char('c') => key.modifiers.contains(KeyModifiers::CONTROL),
+        KeyCode::Char('q') | KeyCode::Esc => true,
+        _ => false,
     }
+}
 
-    fn set_cell(&mut self, x: usize, y: usize, state: CellState) -> Result<(), String> {
-        if x + y * self.width > self.cells.len() {
-            return Err(format!(
-                "Coordinate pair {x},{y} was out of bounds for board size {}x{}",
-                self.width, self.height
-            ));
-        }
-        self.cells[x + y * self.width] = state;
+/// Holds on to the system clipboard for the whole run, as some platforms lose what was copied once it's dropped.
+struct Clipboard {
+    #[cfg(feature = "clipboard")]
+    system: Option<arboard::Clipboard>,
+}
 
-        Ok(())
+impl Clipboard {
+    fn new() -> Self {
+        Self {
+            #[cfg(feature = "clipboard")]
+            system: arboard::Clipboard::new().ok(),
+        }
     }
 
-    /// Ticks the game board, checking if the next set of cells is alive.
-    /// This will return ``true`` if the game managed to tick, else it will return ``false``.
-    fn tick(&mut self) -> Result<bool, String> {
-        let mut changed: Vec<(usize, usize, CellState)> = vec![];
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let neighbors = self.neighbors(x, y)?;
-                let cell = self
-                    .get_cell(x, y)
-                    .ok_or("Somehow the index for the cells were off.")?;
-                match cell {
-                    CellState::Alive => {
-                        // if an alive cell has anything but 2 or 3 neighbors, it dies.
-                        if !(2..=3).contains(&neighbors) {
-                            changed.push((x, y, CellState::Dead));
-                        }
-                    }
-                    CellState::Dead => {
-                        // if a dead cell has 3 neighbors, it becomes alive again.
-                        if neighbors == 3 {
-                            changed.push((x, y, CellState::Alive));
-                        }
-                    }
-                }
+    /// Copies the text to the system clipboard, or prints it to stderr if there isn't one.
+    #[cfg_attr(not(feature = "clipboard"), allow(clippy::unused_self))]
+    fn copy(&mut self, text: &str) {
+        #[cfg(feature = "clipboard")]
+        if let Some(system) = &mut self.system {
+            if system.set_text(text).is_ok() {
+                return;
             }
         }
+        eprint!("{}", text.replace('\n', "\r\n"));
+    }
+}
+
+/// Draws the board along with a status line describing the current generation, wherever the cli asked for it.
+/// The period is shown too once the board has started repeating itself.
+fn print(
+    conway: &Conway,
+    cli: &Cli,
+    generation: u64,
+    period: Option<Cycle>,
+    rate: Option<f64>,
+) -> Result<(), String> {
+    print_frame(
+        conway,
+        TickCounts::of(conway),
+        cli,
+        generation,
+        period,
+        rate,
+    )
+}
+
+/// Draws the board like ``print``, with the status line showing ``counts`` for its last tick.
+fn print_frame(
+    conway: &Conway,
+    counts: TickCounts,
+    cli: &Cli,
+    generation: u64,
+    period: Option<Cycle>,
+    rate: Option<f64>,
+) -> Result<(), String> {
+    let frame = render_ansi_string(conway, counts, cli, generation, period, rate)
+        .map_err(|_| "Unable to draw the board.")?;
+    // The whole frame is written at once, as flushing every cell is slow.
+    let mut stdout = io::stdout().lock();
+    stdout
+        .write_all(frame.as_bytes())
+        .map_err(|_| "Unable to write to stdout.")?;
+    stdout.flush().map_err(|_| "Unable to flush stdout.")?;
+    Ok(())
+}
 
-        if changed.is_empty() {
-            return Ok(false);
+/// Builds the frame that ``print`` draws, escapes for the colors and cursor included.
+fn render_ansi_string(
+    conway: &Conway,
+    counts: TickCounts,
+    cli: &Cli,
+    generation: u64,
+    period: Option<Cycle>,
+    rate: Option<f64>,
+) -> Result<String, fmt::Error> {
+    static OFFSET: OnceLock<usize> = OnceLock::new();
+    let (w, _) = *SIZE.get().expect("Somehow the terminal size wasn't set.");
+    // The left ruler needs room for the biggest row number and a space after it.
+    let margin = if cli.grid {
+        conway.height().to_string().len() + 1
+    } else {
+        0
+    };
+    let offset = OFFSET.get_or_init(|| {
+        let room = w.saturating_sub(drawn_width(conway.is_hex(), conway.width()) + margin);
+        match cli.anchor {
+            Anchor::Left => 0,
+            Anchor::Center => room / 2,
+            Anchor::Right => room,
         }
+    });
+    let options = RenderOptions {
+        offset: *offset,
+        grid: cli.grid,
+        dead_char: cli.dead_char,
+        alive_color: cli.theme.alive_color(),
+        dying_color: cli.theme.dying_color(),
+        dead_color: cli.dead_color,
+        background: cli.theme.background(),
+        heat_colors: cli
+            .heatmap
+            .then(|| array::from_fn(|neighbors| cli.theme.heat_color(neighbors))),
+    };
+    let mut frame = String::new();
+    cursor::MoveTo(0, 0).write_ansi(&mut frame)?;
+    let show_status = !cli.quiet && SHOW_STATUS.load(Ordering::Relaxed);
+    if cli.status == StatusPosition::Top && show_status {
+        write!(
+            frame,
+            "{}\r\n",
+            status_line(conway, counts, cli, generation, period, rate)
+        )?;
+    }
+    frame.push_str(&conway.render_ansi_string(&options));
+    if cli.status == StatusPosition::Bottom && show_status {
+        write!(
+            frame,
+            "{}\r\n",
+            status_line(conway, counts, cli, generation, period, rate)
+        )?;
+    }
+    Ok(frame)
+}
 
-        for (x, y, state) in changed {
-            self.set_cell(x, y, state)?;
+/// Describes the current generation, using the cli's status format if it has one.
+fn status_line(
+    conway: &Conway,
+    counts: TickCounts,
+    cli: &Cli,
+    generation: u64,
+    period: Option<Cycle>,
+    rate: Option<f64>,
+) -> String {
+    let Some(format) = &cli.status_format else {
+        let period = period.map_or_else(String::new, |cycle| format!(" | Period: {cycle}"));
+        // The rate is only worth showing when there's one being aimed for.
+        let rate = match (cli.gps, rate) {
+            (Some(_), Some(rate)) => format!(" | Rate: {rate:.1}/s"),
+            _ => String::new(),
+        };
+        return format!(
+            "Generation: {generation} | Population: {} | Births: {} | Deaths: {}{period}{rate}",
+            conway.population(),
+            counts.births,
+            counts.deaths
+        );
+    };
+    format
+        .replace("{generation}", &generation.to_string())
+        .replace("{population}", &conway.population().to_string())
+        .replace("{births}", &counts.births.to_string())
+        .replace("{deaths}", &counts.deaths.to_string())
+        .replace("{changes}", &counts.changes.to_string())
+        .replace(
+            "{period}",
+            &period.map_or_else(|| "-".to_owned(), |cycle| cycle.period.to_string()),
+        )
+        .replace(
+            "{rate}",
+            &rate.map_or_else(|| "-".to_owned(), |rate| format!("{rate:.1}")),
+        )
+}
+
+/// A logger that appends every record to a file.
+#[cfg(feature = "logging")]
+struct FileLogger(Mutex<File>);
+
+#[cfg(feature = "logging")]
+impl log::Log for FileLogger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        if let Ok(mut file) = self.0.lock() {
+            // There's nowhere sensible to report a failed write while on the alternate screen.
+            let _ = writeln!(file, "[{}] {}", record.level(), record.args());
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.0.lock() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Default options read from a config file, named the same as their flags.
+#[cfg(feature = "config")]
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct Config {
+    automaton: Option<String>,
+    generations: Option<u8>,
+    wrap: Option<bool>,
+    max_gen: Option<u64>,
+    delay: Option<u64>,
+    gps: Option<f64>,
+    quiet: Option<bool>,
+    heatmap: Option<bool>,
+    theme: Option<String>,
+    dead_char: Option<char>,
+    dead_color: Option<String>,
+    anchor: Option<String>,
+    status: Option<String>,
+    status_format: Option<String>,
+    grid: Option<bool>,
+}
+
+#[cfg(feature = "config")]
+impl Config {
+    /// Fills in the options of the cli that ``given`` says weren't on the command line.
+    fn apply(self, cli: &mut Cli, given: impl Fn(&str) -> bool) -> Result<(), String> {
+        if let Some(automaton) = self.automaton.filter(|_| !given("automaton")) {
+            cli.automaton = Some(parse_config_enum("automaton", &automaton)?);
+        }
+        if let Some(generations) = self.generations.filter(|_| !given("generations")) {
+            cli.generations = Some(generations);
+        }
+        if let Some(max_gen) = self.max_gen.filter(|_| !given("max_gen")) {
+            cli.max_gen = max_gen;
         }
+        // A delay on the command line means the rate from the file shouldn't be used, and the other way around.
+        if !given("delay") && !given("gps") {
+            if let Some(delay) = self.delay {
+                cli.delay = delay;
+            }
+            if let Some(gps) = self.gps {
+                cli.gps = Some(parse_gps(&gps.to_string())?);
+            }
+        }
+        if let Some(theme) = self.theme.filter(|_| !given("theme")) {
+            cli.theme = parse_config_enum("theme", &theme)?;
+        }
+        if let Some(dead_char) = self.dead_char.filter(|_| !given("dead_char")) {
+            cli.dead_char = parse_dead_char(&dead_char.to_string())?;
+        }
+        if let Some(dead_color) = self.dead_color.filter(|_| !given("dead_color")) {
+            cli.dead_color = Some(parse_color(&dead_color)?);
+        }
+        if let Some(anchor) = self.anchor.filter(|_| !given("anchor")) {
+            cli.anchor = parse_config_enum("anchor", &anchor)?;
+        }
+        if let Some(status) = self.status.filter(|_| !given("status")) {
+            cli.status = parse_config_enum("status", &status)?;
+        }
+        if let Some(format) = self.status_format.filter(|_| !given("status_format")) {
+            cli.status_format = Some(format);
+        }
+        // Flags can only be turned on from the command line, so the file decides whenever they weren't.
+        // The grid's rulers don't line up with a hex grid's rows, so it's left off of one.
+        let hex = cli.hex;
+        for (flag, value) in [
+            (&mut cli.wrap, self.wrap),
+            (&mut cli.quiet, self.quiet),
+            (&mut cli.heatmap, self.heatmap),
+            (&mut cli.grid, self.grid.filter(|_| !hex)),
+        ] {
+            *flag |= value.unwrap_or(false);
+        }
+        Ok(())
+    }
+}
+
+/// Parses the cli, taking any options that weren't given from the config file if there is one.
+/// Without ``--config``, a missing config file is the same as an empty one.
+#[cfg(feature = "config")]
+fn parse_with_config() -> Result<Cli, String> {
+    use clap::{parser::ValueSource, CommandFactory, FromArgMatches};
+
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).map_err(|e| e.to_string())?;
+    let path = match &cli.config {
+        Some(path) => path.clone(),
+        None => match config_dir() {
+            Some(dir) if dir.join("conway/config.toml").is_file() => dir.join("conway/config.toml"),
+            _ => return Ok(cli),
+        },
+    };
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("Unable to read config file {}: {e}", path.display()))?;
+    let config: Config = toml::from_str(&contents)
+        .map_err(|e| format!("Unable to parse config file {}: {e}", path.display()))?;
+    config.apply(&mut cli, |id| {
+        matches.value_source(id) == Some(ValueSource::CommandLine)
+    })?;
+    Ok(cli)
+}
+
+/// Returns the user's config directory, which is ``$XDG_CONFIG_HOME`` or ``~/.config``, or ``%APPDATA%`` on Windows.
+#[cfg(feature = "config")]
+fn config_dir() -> Option<PathBuf> {
+    let var = |name| std::env::var_os(name).filter(|value| !value.is_empty());
+    if cfg!(windows) {
+        return var("APPDATA").map(PathBuf::from);
+    }
+    var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".config")))
+}
+
+/// Parses the value of one of the config file's options that takes one of a set of names.
+#[cfg(feature = "config")]
+fn parse_config_enum<T: ValueEnum>(key: &str, value: &str) -> Result<T, String> {
+    T::from_str(value, false).map_err(|_| {
+        let names: Vec<String> = T::value_variants()
+            .iter()
+            .filter_map(ValueEnum::to_possible_value)
+            .map(|value| value.get_name().to_owned())
+            .collect();
+        format!(
+            "Invalid {key} {value} in the config file, expected one of {}.",
+            names.join(", ")
+        )
+    })
+}
+
+#[cfg(feature = "logging")]
+fn init_logger(path: &Path) -> Result<(), String> {
+    let file = File::create(path)
+        .map_err(|e| format!("Unable to create log file {}: {e}", path.display()))?;
+    log::set_boxed_logger(Box::new(FileLogger(Mutex::new(file))))
+        .map_err(|_| "Unable to set up logging.")?;
+    log::set_max_level(log::LevelFilter::Info);
+    Ok(())
+}
 
-        Ok(true)
+#[cfg(feature = "logging")]
+fn log_generation(generation: u64, conway: &Conway) {
+    log::info!(
+        "Generation {generation}: population {}, births {}, deaths {}",
+        conway.population(),
+        conway.births(),
+        conway.deaths()
+    );
+}
+
+#[cfg(not(feature = "logging"))]
+fn log_generation(_: u64, _: &Conway) {}
+
+#[cfg(feature = "logging")]
+fn log_stopped(conway: &Conway, generation: u64, exited: bool) {
+    if exited {
+        log::info!("A pattern appears to have exited the board.");
+    }
+    if conway.population() == 0 {
+        log::info!("Board went extinct after {generation} generations.");
+    } else {
+        log::info!("Board stabilized after {generation} generations.");
+    }
+}
+
+#[cfg(not(feature = "logging"))]
+fn log_stopped(_: &Conway, _: u64, _: bool) {}
+
+#[cfg(feature = "logging")]
+fn log_cycle(period: Option<Cycle>, generation: u64) {
+    if let Some(period) = period {
+        log::info!(
+            "Board started repeating every {period} generations after {generation} generations."
+        );
+    }
+}
+
+#[cfg(not(feature = "logging"))]
+fn log_cycle(_: Option<Cycle>, _: u64) {}
+
+#[cfg(feature = "logging")]
+fn log_population_dropped(min_population: usize, generation: u64) {
+    log::info!("Population dropped below {min_population} after {generation} generations.");
+}
+
+#[cfg(not(feature = "logging"))]
+fn log_population_dropped(_: usize, _: u64) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gliders_repeat_moved_over_every_4_generations() {
+        let mut conway = ConwayBuilder::new().size(10, 10).seed(0).build();
+        conway
+            .place(&[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)], (0, 0))
+            .unwrap();
+        let mut cycles = CycleDetector::new(cycle_history(&conway));
+        for generation in 0..4 {
+            assert_eq!(cycles.record(&conway, generation), None);
+            conway.tick().unwrap();
+        }
+        let expected = Cycle {
+            period: 4,
+            shift: (1, 1),
+        };
+        assert_eq!(cycles.record(&conway, 4), Some(expected));
+        conway.tick().unwrap();
+        assert_eq!(cycles.record(&conway, 5), Some(expected));
     }
 }
@@ -0,0 +1,131 @@
+use core::fmt::{self, Write as _};
+
+use crossterm::style::{self, Color, Stylize};
+
+use crate::{CellState, Conway};
+
+/// Resets the colors at the end of each row, so they don't bleed into whatever is drawn after the board.
+const RESET: &str = "\x1B[0m";
+
+/// How ``Conway::render_ansi_string`` draws the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// How many columns of space to leave before each row.
+    pub offset: usize,
+    /// Whether to draw rulers with the 1-based row and column numbers along the top and left of the board.
+    pub grid: bool,
+    /// The character dead cells are drawn with.
+    pub dead_char: char,
+    /// The color of alive cells.
+    pub alive_color: Color,
+    /// The color of cells that are decaying after dying.
+    pub dying_color: Color,
+    /// The color of dead cells, or `None` to leave them uncolored.
+    pub dead_color: Option<Color>,
+    /// The color behind dead cells, or `None` to leave the terminal's background.
+    pub background: Option<Color>,
+    /// Colors alive cells by how many live neighbors they have instead of ``alive_color``, 8 or more using the last.
+    pub heat_colors: Option<[Color; 9]>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            grid: false,
+            dead_char: ' ',
+            alive_color: Color::Green,
+            dying_color: Color::DarkGreen,
+            dead_color: None,
+            background: None,
+            heat_colors: None,
+        }
+    }
+}
+
+impl Conway {
+    /// Returns the board drawn for a terminal, with ``█`` for cells and escapes for their colors.
+    /// Each row ends with ``\r\n``, so the frame can be written as is while the terminal is in raw mode.
+    /// On a hex grid each cell is two characters wide, and odd rows are shifted right by one character.
+    #[must_use]
+    pub fn render_ansi_string(&self, options: &RenderOptions) -> String {
+        let mut frame = String::new();
+        // Writing to a string can't fail, so there's no error to pass on.
+        let _ = self.write_ansi(&mut frame, options);
+        frame
+    }
+
+    fn write_ansi(&self, frame: &mut String, options: &RenderOptions) -> fmt::Result {
+        let cell_width = if self.is_hex() { 2 } else { 1 };
+        // The left ruler needs room for the biggest row number and a space after it.
+        let margin = if options.grid {
+            self.height.to_string().len() + 1
+        } else {
+            0
+        };
+        if options.grid {
+            let ruler = (0..self.width).fold(String::new(), |mut ruler, x| {
+                // Labels start at their column, so skip columns that an earlier label is still covering.
+                if ruler.len() <= x {
+                    ruler.push_str(&ruler_label(x).unwrap_or_else(|| " ".to_owned()));
+                }
+                ruler
+            });
+            let ruler: String = ruler.chars().take(self.width).collect();
+            write!(
+                frame,
+                "{}{}\r\n",
+                " ".repeat(options.offset + margin),
+                ruler.dark_grey()
+            )?;
+        }
+        let alive = "█".repeat(cell_width);
+        let dead_text = options.dead_char.to_string().repeat(cell_width);
+        for (y, row) in self.rows().enumerate() {
+            frame.push_str(&" ".repeat(options.offset));
+            if options.grid {
+                let label = ruler_label(y).unwrap_or_default();
+                write!(frame, "{}", format!("{label:>0$} ", margin - 1).dark_grey())?;
+            }
+            if self.is_hex() && !y.is_multiple_of(2) {
+                frame.push(' ');
+            }
+            for (x, cell) in row.enumerate() {
+                match cell {
+                    CellState::Alive => {
+                        let color = match options.heat_colors {
+                            Some(colors) => colors[self.neighbors(x, y).unwrap_or(0).min(8)],
+                            None => options.alive_color,
+                        };
+                        write!(frame, "{}", alive.as_str().with(color))?;
+                    }
+                    CellState::Dying(_) => {
+                        write!(frame, "{}", alive.as_str().with(options.dying_color))?;
+                    }
+                    CellState::Dead => match (options.dead_color, options.background) {
+                        (None, None) => frame.push_str(&dead_text),
+                        (color, background) => {
+                            let mut dead = style::style(dead_text.as_str());
+                            if let Some(color) = color {
+                                dead = dead.with(color);
+                            }
+                            if let Some(background) = background {
+                                dead = dead.on(background);
+                            }
+                            write!(frame, "{dead}")?;
+                        }
+                    },
+                }
+            }
+            write!(frame, "{RESET}\r\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the label the rulers show for the 0-based coordinate, if it has one.
+/// Coordinates are labelled starting at 1, and then every 10th one.
+fn ruler_label(coordinate: usize) -> Option<String> {
+    let coordinate = coordinate + 1;
+    (coordinate == 1 || coordinate.is_multiple_of(10)).then(|| coordinate.to_string())
+}
@@ -0,0 +1,219 @@
+use core::fmt;
+use std::str::FromStr;
+
+use crate::{CellState, ConwayError};
+
+/// The most decaying states a rule can have, as the written state count also has the alive and dead states.
+const MAX_DYING_STATES: u8 = 253;
+
+/// A life-like rule, describing how many live neighbors make a dead cell come alive
+/// and how many keep an alive cell alive, written like ``B3/S23``.
+///
+/// Rules from the Generations family also have decaying states, which a cell goes through after dying
+/// before it's dead. These are written with the total amount of states on the end, like ``B2/S/C3``.
+/// <https://conwaylife.com/wiki/Generations>
+///
+/// Rules are outer-totalistic by default, where a cell's own state only decides whether the birth or survival counts apply.
+/// A totalistic rule also counts an alive cell itself, so survival counts go from 1 to 9 instead of 0 to 8.
+/// A survival count of 9 can be given either way, though only a totalistic rule can reach it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    birth: [bool; 9],
+    survival: [bool; 10],
+    dying: u8,
+    totalistic: bool,
+}
+
+impl Rule {
+    /// The rule for Conway's game of life, B3/S23.
+    pub const LIFE: Self = Self {
+        birth: [false, false, false, true, false, false, false, false, false],
+        survival: [
+            false, false, true, true, false, false, false, false, false, false,
+        ],
+        dying: 0,
+        totalistic: false,
+    };
+
+    /// The rule for Brian's Brain, B2/S/C3, where every live cell dies and spends a generation dying.
+    /// <https://conwaylife.com/wiki/OCA:Brian%27s_Brain>
+    pub const BRIANS_BRAIN: Self = Self {
+        birth: [false, false, true, false, false, false, false, false, false],
+        survival: [false; 10],
+        dying: 1,
+        totalistic: false,
+    };
+
+    /// A rule for hex grids, B2/S34, where every cell has six neighbors instead of eight.
+    pub const HEX_LIFE: Self = Self {
+        birth: [false, false, true, false, false, false, false, false, false],
+        survival: [
+            false, false, false, true, true, false, false, false, false, false,
+        ],
+        dying: 0,
+        totalistic: false,
+    };
+
+    /// Returns this rule with cells going through ``states`` decaying states after dying, before they're dead.
+    /// This is capped at 253, so that the rule can still be written with at most 255 states.
+    #[must_use]
+    pub fn with_dying_states(mut self, states: u8) -> Self {
+        self.dying = states.min(MAX_DYING_STATES);
+        self
+    }
+
+    /// Returns how many decaying states a cell goes through after dying, which is 0 for life-like rules.
+    #[must_use]
+    pub fn dying_states(&self) -> u8 {
+        self.dying
+    }
+
+    /// Returns this rule with an alive cell counting itself along with its neighbors, or not.
+    /// There's no notation for this, so the rule is written out as the outer-totalistic rule that ticks the same,
+    /// which has every survival count one lower.
+    #[must_use]
+    pub fn with_totalistic(mut self, totalistic: bool) -> Self {
+        self.totalistic = totalistic;
+        self
+    }
+
+    /// Returns whether an alive cell counts itself along with its neighbors.
+    #[must_use]
+    pub fn is_totalistic(&self) -> bool {
+        self.totalistic
+    }
+
+    /// Returns whether a dead cell with the given amount of live neighbors comes alive.
+    #[must_use]
+    pub fn births_with(&self, neighbors: usize) -> bool {
+        self.birth.get(neighbors).copied().unwrap_or(false)
+    }
+
+    /// Returns the state a cell in the given state and with the given amount of live neighbors changes to.
+    #[must_use]
+    pub fn next_state(&self, cell: CellState, neighbors: usize) -> CellState {
+        let count = if self.totalistic && cell == CellState::Alive {
+            neighbors + 1
+        } else {
+            neighbors
+        };
+        let counts: &[bool] = match cell {
+            CellState::Alive => &self.survival,
+            CellState::Dead => &self.birth,
+            // Dying cells decay no matter their neighbors.
+            CellState::Dying(0 | 1) => return CellState::Dead,
+            CellState::Dying(left) => return CellState::Dying(left - 1),
+        };
+        if counts.get(count).copied().unwrap_or(false) {
+            CellState::Alive
+        } else if cell == CellState::Alive && self.dying > 0 {
+            CellState::Dying(self.dying)
+        } else {
+            CellState::Dead
+        }
+    }
+}
+
+/// Decides what a cell becomes on the next tick, from its state and how many live neighbors it has.
+/// ``Rule`` covers life-like and Generations rules, and anything else can implement this
+/// and be given to ``ConwayBuilder::custom_rule``.
+pub trait LifeRule: fmt::Debug + Send + Sync {
+    /// Returns the state a cell in the ``current`` state and with ``live_neighbors`` live neighbors changes to.
+    fn next_state(&self, current: CellState, live_neighbors: usize) -> CellState;
+}
+
+impl LifeRule for Rule {
+    fn next_state(&self, current: CellState, live_neighbors: usize) -> CellState {
+        Rule::next_state(self, current, live_neighbors)
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self::LIFE
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = |counts: &[bool]| -> String {
+            (0..counts.len())
+                .filter(|&n| counts[n])
+                .map(|n| char::from(b'0' + n as u8))
+                .collect()
+        };
+        // An alive cell counting itself needs one fewer live neighbor to reach the same count.
+        let survival = if self.totalistic {
+            &self.survival[1..]
+        } else {
+            &self.survival[..]
+        };
+        write!(f, "B{}/S{}", digits(&self.birth), digits(survival))?;
+        if self.dying > 0 {
+            // The count includes the alive and dead states.
+            write!(f, "/C{}", u16::from(self.dying) + 2)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Rule {
+    type Err = ConwayError;
+
+    /// Parses a rule in either ``B3/S23`` notation or the older ``23/3`` survival/birth notation.
+    /// Either can be followed by the amount of states for a Generations rule, like ``B2/S/C3`` or ``/2/3``.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rule = s.trim().to_ascii_uppercase();
+        let mut parts = rule.split('/').collect::<Vec<&str>>();
+        let dying = match parts[..] {
+            [_, _, states] => {
+                parts.pop();
+                match states.trim_start_matches('C').parse::<u8>() {
+                    Ok(states @ 2..) => states - 2,
+                    _ => {
+                        return Err(ConwayError::InvalidRule(format!(
+                            "Invalid amount of states in rule {s}, expected a number from 2 to 255."
+                        )))
+                    }
+                }
+            }
+            _ => 0,
+        };
+        let (birth, survival) = match parts[..] {
+            [first, second] => match (first.chars().next(), second.chars().next()) {
+                (Some('B'), Some('S')) => (&first[1..], &second[1..]),
+                (Some('S'), Some('B')) => (&second[1..], &first[1..]),
+                // Without any letters, it's the survival/birth notation.
+                _ => (second, first),
+            },
+            _ => {
+                return Err(ConwayError::InvalidRule(format!(
+                    "Unable to parse rule {s}, expected something like B3/S23."
+                )))
+            }
+        };
+
+        Ok(Self {
+            birth: parse_counts(birth, s)?,
+            survival: parse_counts(survival, s)?,
+            dying,
+            totalistic: false,
+        })
+    }
+}
+
+/// Parses the neighbor counts in one half of ``rule``, which go up to ``N - 1``.
+fn parse_counts<const N: usize>(digits: &str, rule: &str) -> Result<[bool; N], ConwayError> {
+    let mut counts = [false; N];
+    for digit in digits.chars() {
+        match digit.to_digit(10).map(|n| n as usize) {
+            Some(n) if n < N => counts[n] = true,
+            _ => {
+                return Err(ConwayError::InvalidRule(format!(
+                    "Invalid neighbor count {digit} in rule {rule}."
+                )))
+            }
+        }
+    }
+    Ok(counts)
+}